@@ -40,7 +40,11 @@ use std::sync::Arc;
 async fn send_commands(mut to_server: net::TcpStream) -> ChatResult<()> {
     println!("Commands:\n\
               join GROUP\n\
+              leave GROUP\n\
+              list\n\
               post GROUP MESSAGE...\n\
+              name NICK\n\
+              msg RECIPIENT MESSAGE...\n\
               Type Control-D (on Unix) or Control-Z (on Windows)\
               to close the connection.");
 
@@ -60,27 +64,118 @@ async fn send_commands(mut to_server: net::TcpStream) -> ChatResult<()> {
     Ok(())
 }
 
+type ParseFn = fn(&str) -> Option<FromClient>;
+
+/// The commands the client understands, each paired with the function that parses the rest of the
+/// line after the command name. Supporting a new command means adding one entry here.
+const COMMANDS: &[(&str, ParseFn)] = &[
+    ("join", parse_join),
+    ("leave", parse_leave),
+    ("list", parse_list),
+    ("post", parse_post),
+    ("name", parse_name),
+    ("msg", parse_msg),
+];
+
 fn parse_command(line: &str) -> Option<FromClient> {
     let (command, rest) = get_next_token(line)?;
-    if command == "post" {
-        let (group, rest) = get_next_token(rest)?;
-        let message = rest.trim_start().to_string();
-        return Some(FromClient::Post{
-            group_name: Arc::new(group.to_string()),
-            message: Arc::new(message),
-        });
-    } else if command == "join" {
-        let (group, rest) = get_next_token(rest)?;
-        if !rest.trim_start().is_empty() {
-            return None;
+    match COMMANDS.iter().find(|(name, _)| *name == command) {
+        Some((_, parse)) => parse(rest),
+        None => {
+            eprintln!("Unrecognized command: {:?}", line);
+            None
         }
-        return Some(FromClient::Join {
-            group_name: Arc::new(group.to_string()),
-        });
-    } else {
-        eprintln!("Unrecognized command: {:?}", line);
+    }
+}
+
+fn parse_join(rest: &str) -> Option<FromClient> {
+    let (group, rest) = get_next_token(rest)?;
+    if !rest.trim_start().is_empty() {
+        return None;
+    }
+    Some(FromClient::Join {
+        group_name: Arc::new(group.to_string()),
+    })
+}
+
+fn parse_leave(rest: &str) -> Option<FromClient> {
+    let (group, rest) = get_next_token(rest)?;
+    if !rest.trim_start().is_empty() {
+        return None;
+    }
+    Some(FromClient::Leave {
+        group_name: Arc::new(group.to_string()),
+    })
+}
+
+fn parse_list(rest: &str) -> Option<FromClient> {
+    if !rest.trim_start().is_empty() {
         return None;
     }
+    Some(FromClient::ListGroups)
+}
+
+fn parse_post(rest: &str) -> Option<FromClient> {
+    let (group, rest) = get_next_token(rest)?;
+    let message = rest.trim_start().to_string();
+    Some(FromClient::Post {
+        group_name: Arc::new(group.to_string()),
+        message: Arc::new(message),
+    })
+}
+
+fn parse_name(rest: &str) -> Option<FromClient> {
+    let (name, rest) = get_next_token(rest)?;
+    if !rest.trim_start().is_empty() {
+        return None;
+    }
+    Some(FromClient::SetName {
+        name: Arc::new(name.to_string()),
+    })
+}
+
+fn parse_msg(rest: &str) -> Option<FromClient> {
+    let (recipient, rest) = get_next_token(rest)?;
+    let message = rest.trim_start().to_string();
+    Some(FromClient::Direct {
+        recipient: Arc::new(recipient.to_string()),
+        message: Arc::new(message),
+    })
+}
+
+#[test]
+fn test_parse_command() {
+    assert_eq!(parse_command("join Dogs"),
+               Some(FromClient::Join { group_name: Arc::new("Dogs".to_string()) }));
+    assert_eq!(parse_command("join Dogs Cats"), None);
+    assert_eq!(parse_command("join"), None);
+
+    assert_eq!(parse_command("leave Dogs"),
+               Some(FromClient::Leave { group_name: Arc::new("Dogs".to_string()) }));
+    assert_eq!(parse_command("leave"), None);
+
+    assert_eq!(parse_command("list"), Some(FromClient::ListGroups));
+    assert_eq!(parse_command("list Dogs"), None);
+
+    assert_eq!(parse_command("  post Dogs   Samoyeds rock!"),
+               Some(FromClient::Post {
+                   group_name: Arc::new("Dogs".to_string()),
+                   message: Arc::new("Samoyeds rock!".to_string()),
+               }));
+    assert_eq!(parse_command("post"), None);
+
+    assert_eq!(parse_command("name rover"),
+               Some(FromClient::SetName { name: Arc::new("rover".to_string()) }));
+    assert_eq!(parse_command("name rover fido"), None);
+    assert_eq!(parse_command("msg fido Fetch?"),
+               Some(FromClient::Direct {
+                   recipient: Arc::new("fido".to_string()),
+                   message: Arc::new("Fetch?".to_string()),
+               }));
+    assert_eq!(parse_command("msg"), None);
+
+    assert_eq!(parse_command("shout Dogs woof"), None);
+    assert_eq!(parse_command(""), None);
 }
 
 /// Given a string `input`, return `Some((token, rest))`, where `token` is the first run of non-whitespace
@@ -110,8 +205,19 @@ async fn handle_replies(from_server: net::TcpStream) -> ChatResult<()> {
 
     while let Some(reply) = reply_stream.next().await {
         match reply? {
-            FromServer::Message { group_name, message} => {
-                println!("message posted to {group_name}: {message}");
+            FromServer::Message { group_name, message, timestamp } => {
+                println!("[{timestamp}] message posted to {group_name}: {message}");
+            }
+            FromServer::Joined { group_name } => {
+                println!("joined {group_name}");
+            }
+            FromServer::DirectMessage { sender, message } => {
+                println!("direct message from {sender}: {message}");
+            }
+            FromServer::GroupList { names } => {
+                for name in names {
+                    println!("{name}");
+                }
             }
             FromServer::Error(message) => {
                 println!("error from server: {message}");
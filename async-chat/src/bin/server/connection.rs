@@ -1,15 +1,25 @@
 use async_chat::{FromClient, FromServer, utils::{self, ChatResult}};
 use async_std::{prelude::*, io::BufReader, net::TcpStream, sync::{Arc, Mutex}};
 
+use crate::group::Group;
 use crate::group_table::GroupTable;
+use crate::name_table::NameTable;
 
 /// This is almost a mirror image of the client's `handle_replies` function: the bulk of the code is
 /// a loop handling an incoming stream of FromClient values, built from a buffered TCP stream with
 /// receive_as_json. If an error occurs, we generate a `FromServer::Error` packet to convey the bad
 /// news back to the client.
-pub async fn serve(socket: TcpStream, groups : Arc<GroupTable>) -> ChatResult<()>
+///
+/// Each connection may also pick a nickname, which other clients use to address direct messages to
+/// it. The `_registration` guard releases that name however `serve` returns, and the `memberships`
+/// guard likewise leaves every group the connection joined.
+pub async fn serve(socket: TcpStream, groups : Arc<GroupTable>, names: Arc<NameTable>)
+    -> ChatResult<()>
 {
     let outbound = Arc::new(Outbound::new(socket.clone()));
+    let _registration = NameRegistration { names: &names, outbound: &outbound };
+    let mut memberships = Memberships { groups: Vec::new(), outbound: &outbound };
+    let mut nickname: Option<Arc<String>> = None;
 
     let buffered = BufReader::new(socket);
     let mut from_client = utils::receive_as_json(buffered);
@@ -19,11 +29,65 @@ pub async fn serve(socket: TcpStream, groups : Arc<GroupTable>) -> ChatResult<()
 
         let result = match request {
             FromClient::Join{group_name} => {
-                let group = groups.get_or_create(group_name);
+                let group = groups.get_or_create(group_name.clone());
                 group.join(outbound.clone());
+                if !memberships.groups.iter().any(|joined| Arc::ptr_eq(joined, &group)) {
+                    memberships.groups.push(group);
+                }
+                outbound.send(FromServer::Joined { group_name }).await?;
+                Ok(())
+            }
+
+            FromClient::Leave { group_name } => {
+                match groups.get(&group_name) {
+                    Some(group) => {
+                        if group.leave(&outbound) {
+                            Ok(())
+                        } else {
+                            Err(format!("Not a member of group '{group_name}'"))
+                        }
+                    }
+                    None => {
+                        Err(format!("Group '{group_name}' does not exist"))
+                    }
+                }
+            }
+
+            FromClient::ListGroups => {
+                outbound.send(FromServer::GroupList { names: groups.names() }).await?;
                 Ok(())
             }
 
+            FromClient::SetName { name } => {
+                if names.register(name.clone(), &outbound) {
+                    nickname = Some(name);
+                    Ok(())
+                } else {
+                    Err(format!("The name '{name}' is already taken"))
+                }
+            }
+
+            FromClient::Direct { recipient, message } => {
+                match (&nickname, names.get(&recipient)) {
+                    (None, _) => {
+                        Err("Choose a name before sending direct messages".to_string())
+                    }
+                    (Some(_), None) => {
+                        Err(format!("No client is named '{recipient}'"))
+                    }
+                    (Some(sender), Some(to_recipient)) => {
+                        let packet = FromServer::DirectMessage {
+                            sender: sender.clone(),
+                            message,
+                        };
+                        // A broken recipient socket is the recipient's problem, not the
+                        // sender's, so report it rather than dropping this connection.
+                        to_recipient.send(packet).await
+                            .map_err(|_| format!("could not deliver to '{recipient}'"))
+                    }
+                }
+            }
+
             FromClient::Post { group_name, message } => {
                 match groups.get(&group_name) {
                     Some(group) => {
@@ -46,6 +110,30 @@ pub async fn serve(socket: TcpStream, groups : Arc<GroupTable>) -> ChatResult<()
     Ok(())
 }
 
+struct NameRegistration<'a> {
+    names: &'a NameTable,
+    outbound: &'a Arc<Outbound>,
+}
+
+impl Drop for NameRegistration<'_> {
+    fn drop(&mut self) {
+        self.names.release(self.outbound);
+    }
+}
+
+struct Memberships<'a> {
+    groups: Vec<Arc<Group>>,
+    outbound: &'a Arc<Outbound>,
+}
+
+impl Drop for Memberships<'_> {
+    fn drop(&mut self) {
+        for group in &self.groups {
+            group.leave(self.outbound);
+        }
+    }
+}
+
 pub struct Outbound(Mutex<TcpStream>);
 
 impl Outbound {
@@ -60,3 +148,51 @@ impl Outbound {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_std::{net::{Shutdown, TcpListener}, task};
+
+    #[test]
+    fn test_direct_to_broken_recipient() {
+        task::block_on(async {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let address = listener.local_addr().unwrap();
+            let groups = Arc::new(GroupTable::new());
+            let names = Arc::new(NameTable::new());
+
+            // Register a recipient whose socket can no longer be written to.
+            let recipient_socket = TcpStream::connect(address).await.unwrap();
+            let _recipient_peer = listener.accept().await.unwrap();
+            recipient_socket.shutdown(Shutdown::Write).unwrap();
+            let recipient = Arc::new(Outbound::new(recipient_socket));
+            assert!(names.register(Arc::new("bob".to_string()), &recipient));
+
+            let mut sender = TcpStream::connect(address).await.unwrap();
+            let (socket, _) = listener.accept().await.unwrap();
+            let server = task::spawn(serve(socket, groups, names.clone()));
+
+            let mut replies = utils::receive_as_json(BufReader::new(sender.clone()));
+            for request in [
+                FromClient::SetName { name: Arc::new("alice".to_string()) },
+                FromClient::Direct {
+                    recipient: Arc::new("bob".to_string()),
+                    message: Arc::new("hello".to_string()),
+                },
+            ] {
+                utils::send_as_json(&mut sender, &request).await.unwrap();
+            }
+            let reply: FromServer = replies.next().await.unwrap().unwrap();
+            assert_eq!(reply, FromServer::Error("could not deliver to 'bob'".to_string()));
+
+            // The sender is still connected and being served.
+            utils::send_as_json(&mut sender, &FromClient::ListGroups).await.unwrap();
+            let reply: FromServer = replies.next().await.unwrap().unwrap();
+            assert_eq!(reply, FromServer::GroupList { names: vec![] });
+
+            sender.shutdown(Shutdown::Both).unwrap();
+            server.await.unwrap();
+        });
+    }
+}
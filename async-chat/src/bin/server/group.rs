@@ -1,7 +1,8 @@
-use async_std::task;
+use async_std::{prelude::*, task};
 use crate::connection::Outbound;
-use std::sync::Arc;
-use tokio::sync::{broadcast, broadcast::error::RecvError};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::{broadcast, broadcast::error::RecvError, Notify};
 use async_chat::FromServer;
 
 /// In our server, the group::Group type represents a chat group. This type only needs to support the
@@ -25,40 +26,85 @@ use async_chat::FromServer;
 /// it is created, the oldest messages get dropped. Any consumers who couldn't keep up get an error
 /// the next time they try to get their next message, and the channel catches them up to the oldest
 /// message still available.
+///
+/// To let a member `leave`, the group also remembers each member's `Outbound` alongside a `Notify`
+/// shared with that member's subscriber task. Leaving removes the entry and signals the `Notify`;
+/// the subscriber task waits for that signal alongside the next message, so it exits right away
+/// rather than when the next message is posted, dropping its `Outbound`. `connection::serve` leaves
+/// every group it joined when it returns, so a client that disconnects without leaving doesn't keep
+/// its socket open in the group forever.
 pub struct Group {
     name: Arc<String>,
-    sender: broadcast::Sender<Arc<String>>
+    sender: broadcast::Sender<(u64, Arc<String>)>,
+    members: Mutex<Vec<(Arc<Outbound>, Arc<Notify>)>>,
 }
 
 impl Group {
     pub fn new(name: Arc<String>) -> Group {
         let (sender, _receiver) = broadcast::channel(1000);
-        Group{name, sender}
+        Group{name, sender, members: Mutex::new(Vec::new())}
     }
 
     pub fn join(&self, outbound: Arc<Outbound>) {
         let receiver = self.sender.subscribe();
+        let left = Arc::new(Notify::new());
+        self.members.lock().unwrap().push((outbound.clone(), left.clone()));
+
+        task::spawn(handle_subscriber(self.name.clone(), receiver, outbound, left));
+    }
 
-        task::spawn(handle_subscriber(self.name.clone(), receiver, outbound));
+    /// Remove every subscription `outbound` holds on this group. Return false if it wasn't a member.
+    pub fn leave(&self, outbound: &Arc<Outbound>) -> bool {
+        let mut members = self.members.lock().unwrap();
+        let before = members.len();
+        members.retain(|(member, left)| {
+            if Arc::ptr_eq(member, outbound) {
+                // If the subscriber isn't waiting right now, `notify_one` saves the signal for the
+                // next time it does.
+                left.notify_one();
+                false
+            } else {
+                true
+            }
+        });
+        members.len() != before
     }
 
     pub fn post(&self, message: Arc<String>) {
         // This only returns an error when there are no subscribers. A connection's outgoing side can
         // exit, dropping its subscription, slightly before its incoming side, which may end up trying
         // to send a message to an empty group.
-        let _ignored = self.sender.send(message);
+        let _ignored = self.sender.send((unix_timestamp(), message));
     }
 }
 
+/// The current time in seconds since the Unix epoch, stamped on each message as it's posted.
+fn unix_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
 
-async fn handle_subscriber(group_name: Arc<String>, mut receiver: broadcast::Receiver<Arc<String>>,
-                            outbound: Arc<Outbound>)
+async fn handle_subscriber(group_name: Arc<String>,
+                            mut receiver: broadcast::Receiver<(u64, Arc<String>)>,
+                            outbound: Arc<Outbound>, left: Arc<Notify>)
 {
     loop {
-        let packet = match receiver.recv().await {
-            Ok(message) => FromServer::Message {
+        let next_message = async { Some(receiver.recv().await) };
+        let leaving = async {
+            left.notified().await;
+            None
+        };
+        let received = match next_message.race(leaving).await {
+            Some(received) => received,
+            None => break,
+        };
+
+        let packet = match received {
+            Ok((timestamp, message)) => FromServer::Message {
                 group_name: group_name.clone(),
-                message:message.clone(),
+                message,
+                timestamp,
             },
             Err(RecvError::Lagged(n)) => FromServer::Error(
                 format!("Dropped {n} messages from {group_name}.")
@@ -23,4 +23,11 @@ impl GroupTable {
         self.0.lock().unwrap().entry(name.clone()).or_insert_with(|| Arc::new(Group::new(name)))
             .clone()
     }
+
+    /// Return the names of all the groups, sorted.
+    pub fn names(&self) -> Vec<Arc<String>> {
+        let mut names: Vec<Arc<String>> = self.0.lock().unwrap().keys().cloned().collect();
+        names.sort();
+        names
+    }
 }
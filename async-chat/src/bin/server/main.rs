@@ -6,6 +6,7 @@ use tokio::net;
 mod group_table;
 mod connection;
 mod group;
+mod name_table;
 
 use connection::serve;
 
@@ -16,7 +17,8 @@ use connection::serve;
 ///
 /// For each incoming connection, we spawn an asynchronous task running the `connection::serve`
 /// function. Each task also receives a reference to a `GroupTable` value representing our server's
-/// current list of chat groups, shared by all the connections via an Arc reference-counted pointer.
+/// current list of chat groups, shared by all the connections via an Arc reference-counted pointer,
+/// and likewise a `NameTable` of the nicknames connected clients have chosen.
 ///
 /// If `connection::serve` returns an error, we log a message to the standard error output and let
 /// the task exit. Other connections continue to run as usual.
@@ -24,6 +26,7 @@ fn main() -> ChatResult<()> {
     let address = std::env::args().nth(1).expect("Usage: server ADDRESS");
 
     let chat_group_table = Arc::new(group_table::GroupTable::new());
+    let chat_name_table = Arc::new(name_table::NameTable::new());
 
     async_std::task::block_on(async {
         use async_std::{net, task};
@@ -34,8 +37,9 @@ fn main() -> ChatResult<()> {
         while let Some(socket_result) = new_connections.next().await {
             let socket = socket_result?;
             let groups = chat_group_table.clone();
+            let names = chat_name_table.clone();
             task::spawn(async {
-                log_error(serve(socket, groups).await);
+                log_error(serve(socket, groups, names).await);
             });
         }
         Ok(())
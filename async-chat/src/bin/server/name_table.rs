@@ -0,0 +1,35 @@
+use crate::connection::Outbound;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// A `NameTable` maps the nicknames clients have chosen with `FromClient::SetName` to their
+/// connections, so that a direct message can be routed to its recipient. Like `GroupTable`, it's a
+/// plain mutex-protected hash table: no operation holds the lock across an `await`.
+pub struct NameTable(Mutex<HashMap<Arc<String>, Arc<Outbound>>>);
+
+impl NameTable {
+    pub fn new() -> NameTable {
+        NameTable(Mutex::new(HashMap::new()))
+    }
+
+    pub fn get(&self, name: &String) -> Option<Arc<Outbound>> {
+        self.0.lock().unwrap().get(name).cloned()
+    }
+
+    /// Give `outbound` the nickname `name`, releasing any name it held before. Return false, and
+    /// change nothing, if some other connection already holds `name`.
+    pub fn register(&self, name: Arc<String>, outbound: &Arc<Outbound>) -> bool {
+        let mut table = self.0.lock().unwrap();
+        if let Some(holder) = table.get(&name) {
+            return Arc::ptr_eq(holder, outbound);
+        }
+        table.retain(|_, holder| !Arc::ptr_eq(holder, outbound));
+        table.insert(name, outbound.clone());
+        true
+    }
+
+    /// Release whatever name `outbound` holds, so others may take it once its connection closes.
+    pub fn release(&self, outbound: &Arc<Outbound>) {
+        self.0.lock().unwrap().retain(|_, holder| !Arc::ptr_eq(holder, outbound));
+    }
+}
@@ -4,14 +4,23 @@ use std::sync::Arc;
 pub mod utils;
 
 /// The `FromClient` enum represents the packets a client can send to the server: it can ask to join
-/// a group and post messages to any group it has joined.
+/// a group, leave a group it has joined, post messages to any group it has joined, and ask which
+/// groups exist. It can also choose a nickname, and send private messages to another client by
+/// theirs.
 #[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub enum FromClient {
     Join {group_name: Arc<String>},
+    Leave {group_name: Arc<String>},
+    ListGroups,
     Post {
         group_name: Arc<String>,
         message: Arc<String>
-    }
+    },
+    SetName {name: Arc<String>},
+    Direct {
+        recipient: Arc<String>,
+        message: Arc<String>
+    },
 }
 
 /// `FromServer` represents what the server can send back: messages posted to some group, and error
@@ -21,6 +30,17 @@ pub enum FromClient {
 pub enum FromServer {
     Message {
         group_name: Arc<String>,
+        message: Arc<String>,
+        /// When the message was posted, in seconds since the Unix epoch.
+        timestamp: u64,
+    },
+    /// Acknowledges a `FromClient::Join`, once the client is a member of the group.
+    Joined {group_name: Arc<String>},
+    /// Answers a `FromClient::ListGroups` with the names of all the groups on the server.
+    GroupList {names: Vec<Arc<String>>},
+    /// A private message from the client that chose the nickname `sender`.
+    DirectMessage {
+        sender: Arc<String>,
         message: Arc<String>
     },
     Error(String),
@@ -44,4 +64,85 @@ mod tests {
 
         assert_eq!(serde_json::from_str::<FromClient>(&json).unwrap(), from_client);
     }
+
+    #[test]
+    fn test_fromclient_leave_json() {
+        let from_client = FromClient::Leave {
+            group_name: Arc::new("Dogs".to_string()),
+        };
+
+        let json = serde_json::to_string(&from_client).unwrap();
+
+        assert_eq!(json, r#"{"Leave":{"group_name":"Dogs"}}"#);
+
+        assert_eq!(serde_json::from_str::<FromClient>(&json).unwrap(), from_client);
+    }
+
+    #[test]
+    fn test_fromserver_json() {
+        let message = FromServer::Message {
+            group_name: Arc::new("Dogs".to_string()),
+            message: Arc::new("Samoyeds rock!".to_string()),
+            timestamp: 1_600_000_000,
+        };
+        let json = serde_json::to_string(&message).unwrap();
+        assert_eq!(json,
+                   r#"{"Message":{"group_name":"Dogs","message":"Samoyeds rock!","timestamp":1600000000}}"#);
+        assert_eq!(serde_json::from_str::<FromServer>(&json).unwrap(), message);
+
+        // Messages from before timestamps were added are rejected, not misread.
+        let old_format = r#"{"Message":{"group_name":"Dogs","message":"Samoyeds rock!"}}"#;
+        let error = serde_json::from_str::<FromServer>(old_format).unwrap_err();
+        assert!(error.to_string().contains("missing field `timestamp`"));
+
+        let joined = FromServer::Joined {
+            group_name: Arc::new("Dogs".to_string()),
+        };
+        let json = serde_json::to_string(&joined).unwrap();
+        assert_eq!(json, r#"{"Joined":{"group_name":"Dogs"}}"#);
+        assert_eq!(serde_json::from_str::<FromServer>(&json).unwrap(), joined);
+    }
+
+    #[test]
+    fn test_grouplist_json() {
+        let empty = FromServer::GroupList { names: vec![] };
+        let json = serde_json::to_string(&empty).unwrap();
+        assert_eq!(json, r#"{"GroupList":{"names":[]}}"#);
+        assert_eq!(serde_json::from_str::<FromServer>(&json).unwrap(), empty);
+
+        let groups = FromServer::GroupList {
+            names: vec![Arc::new("Cats".to_string()), Arc::new("Dogs".to_string())],
+        };
+        let json = serde_json::to_string(&groups).unwrap();
+        assert_eq!(json, r#"{"GroupList":{"names":["Cats","Dogs"]}}"#);
+        assert_eq!(serde_json::from_str::<FromServer>(&json).unwrap(), groups);
+
+        let request = serde_json::to_string(&FromClient::ListGroups).unwrap();
+        assert_eq!(request, r#""ListGroups""#);
+        assert_eq!(serde_json::from_str::<FromClient>(&request).unwrap(), FromClient::ListGroups);
+    }
+
+    #[test]
+    fn test_direct_message_json() {
+        let set_name = FromClient::SetName { name: Arc::new("rover".to_string()) };
+        let json = serde_json::to_string(&set_name).unwrap();
+        assert_eq!(json, r#"{"SetName":{"name":"rover"}}"#);
+        assert_eq!(serde_json::from_str::<FromClient>(&json).unwrap(), set_name);
+
+        let direct = FromClient::Direct {
+            recipient: Arc::new("fido".to_string()),
+            message: Arc::new("Fetch?".to_string()),
+        };
+        let json = serde_json::to_string(&direct).unwrap();
+        assert_eq!(json, r#"{"Direct":{"recipient":"fido","message":"Fetch?"}}"#);
+        assert_eq!(serde_json::from_str::<FromClient>(&json).unwrap(), direct);
+
+        let delivered = FromServer::DirectMessage {
+            sender: Arc::new("rover".to_string()),
+            message: Arc::new("Fetch?".to_string()),
+        };
+        let json = serde_json::to_string(&delivered).unwrap();
+        assert_eq!(json, r#"{"DirectMessage":{"sender":"rover","message":"Fetch?"}}"#);
+        assert_eq!(serde_json::from_str::<FromServer>(&json).unwrap(), delivered);
+    }
 }
@@ -49,3 +49,110 @@ pub fn receive_as_json<S, P>(inbound: S) -> impl Stream<Item = ChatResult<P>>
             Ok(parsed)
         })
 }
+
+/// The largest packet, in bytes of JSON, that `send_as_json_framed` will send or
+/// `receive_as_json_framed` will accept. The length prefix comes from the other end of the
+/// connection, so without a limit a single bad header could make us allocate 4 GiB.
+pub const MAX_FRAME_LEN: usize = 1 << 20;
+
+/// `send_as_json` ends each packet with a newline, so it relies on the JSON itself never containing
+/// one. That holds for what `serde_json::to_string` produces, but framing each packet with its
+/// length instead doesn't depend on the encoding at all: we write the number of bytes of JSON as a
+/// 4-byte big-endian integer, followed by the JSON itself.
+pub async fn send_as_json_framed<S, P>(outbound: &mut S, packet: &P) -> ChatResult<()>
+where
+    S: async_std::io::Write + Unpin,
+    P: Serialize,
+{
+    let json = serde_json::to_vec(&packet)?;
+    if json.len() > MAX_FRAME_LEN {
+        return Err(format!("packet of {} bytes exceeds the {MAX_FRAME_LEN}-byte limit",
+                           json.len()).into());
+    }
+    let length = u32::try_from(json.len())?;
+    outbound.write_all(&length.to_be_bytes()).await?;
+    outbound.write_all(&json).await?;
+    Ok(())
+}
+
+/// Read one packet written by `send_as_json_framed`: a 4-byte big-endian length, and then exactly
+/// that many bytes of JSON. Return `Ok(None)` if the stream ends cleanly before the next packet; a
+/// stream that ends partway through a packet is an error.
+pub async fn receive_as_json_framed<S, P>(inbound: &mut S) -> ChatResult<Option<P>>
+where
+    S: async_std::io::Read + Unpin,
+    P: DeserializeOwned,
+{
+    let mut length = [0_u8; 4];
+    let mut filled = 0;
+    while filled < length.len() {
+        match inbound.read(&mut length[filled..]).await? {
+            0 if filled == 0 => return Ok(None),
+            0 => return Err("stream ended in the middle of a packet length".into()),
+            n => filled += n,
+        }
+    }
+
+    let length = u32::from_be_bytes(length) as usize;
+    if length > MAX_FRAME_LEN {
+        return Err(format!("packet length {length} exceeds the {MAX_FRAME_LEN}-byte limit").into());
+    }
+
+    let mut json = vec![0_u8; length];
+    inbound.read_exact(&mut json).await?;
+    Ok(Some(serde_json::from_slice::<P>(&json)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FromClient;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_framed_round_trip() {
+        async_std::task::block_on(async {
+            let packet = FromClient::Post {
+                group_name: Arc::new("Dogs".to_string()),
+                message: Arc::new("Samoyeds\nrock!\n".to_string()),
+            };
+
+            let mut buffer = Vec::new();
+            send_as_json_framed(&mut buffer, &packet).await.unwrap();
+            send_as_json_framed(&mut buffer, &packet).await.unwrap();
+
+            let mut inbound = &buffer[..];
+            for _ in 0..2 {
+                let received = receive_as_json_framed::<_, FromClient>(&mut inbound).await.unwrap();
+                assert_eq!(received.as_ref(), Some(&packet));
+            }
+            assert!(receive_as_json_framed::<_, FromClient>(&mut inbound).await.unwrap().is_none());
+
+            let mut truncated = &buffer[..buffer.len() / 2 + 2];
+            assert!(receive_as_json_framed::<_, FromClient>(&mut truncated).await.unwrap().is_some());
+            assert!(receive_as_json_framed::<_, FromClient>(&mut truncated).await.is_err());
+        });
+    }
+
+    #[test]
+    fn test_framed_oversized() {
+        async_std::task::block_on(async {
+            // A length prefix claiming almost 4 GiB is refused without reading any further.
+            let mut inbound = &[0xff_u8, 0xff, 0xff, 0xff, b'{'][..];
+            let error = receive_as_json_framed::<_, FromClient>(&mut inbound).await.unwrap_err();
+            assert!(error.to_string().contains("exceeds"));
+            assert_eq!(inbound, b"{");
+
+            let mut inbound = &((MAX_FRAME_LEN + 1) as u32).to_be_bytes()[..];
+            assert!(receive_as_json_framed::<_, FromClient>(&mut inbound).await.is_err());
+
+            let packet = FromClient::Post {
+                group_name: Arc::new("Dogs".to_string()),
+                message: Arc::new("woof".repeat(MAX_FRAME_LEN / 4)),
+            };
+            let mut buffer = Vec::new();
+            assert!(send_as_json_framed(&mut buffer, &packet).await.is_err());
+            assert!(buffer.is_empty());
+        });
+    }
+}
@@ -1,7 +1,9 @@
 use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{self, RecvTimeoutError};
 use std::task::{Waker, Context, Poll};
 use std::future::Future;
 use std::pin::Pin;
+use std::time::{Duration, Instant};
 
 pub fn spawn_blocking<T, F>(closure: F) -> SpawnBlocking<T>
 where F: FnOnce() -> T,
@@ -32,6 +34,20 @@ where F: FnOnce() -> T,
     SpawnBlocking(inner)
 }
 
+/// If the closure passed to `spawn_blocking` panics, its thread dies without ever storing a value,
+/// and the future waits forever. `try_spawn_blocking` catches the panic instead, so the future
+/// resolves to `Err` holding the panic's payload, just as `JoinHandle::join` would return.
+///
+/// `catch_unwind` wants an `UnwindSafe` closure, promising that nothing it can see will be left
+/// half-updated by the panic. The closure is ours to consume, and nobody can observe its state
+/// after the panic, so wrapping it in `AssertUnwindSafe` is sound here.
+pub fn try_spawn_blocking<T, F>(closure: F) -> SpawnBlocking<std::thread::Result<T>>
+where F: FnOnce() -> T,
+      F: Send + 'static,
+      T: Send + 'static,
+{
+    spawn_blocking(move || std::panic::catch_unwind(std::panic::AssertUnwindSafe(closure)))
+}
 
 /// `SpawnBlocking<T>` is a future of the closure's return value.
 pub struct SpawnBlocking<T>(Arc<Mutex<Shared<T>>>);
@@ -64,6 +80,121 @@ impl<T: Send> Future for SpawnBlocking<T> {
     }
 }
 
+/// A future that waits for every one of a set of `SpawnBlocking` futures, and resolves to their
+/// results, in the same order as the futures were given.
+///
+/// Each child's result stays in its `Shared` until they're all done, so `JoinAll` holds nothing but
+/// `Arc`s, and is `Unpin` whatever `T` is.
+pub struct JoinAll<T> {
+    children: Vec<SpawnBlocking<T>>,
+}
+
+pub fn join_all<T>(children: Vec<SpawnBlocking<T>>) -> JoinAll<T> {
+    JoinAll { children }
+}
+
+impl<T: Send> Future for JoinAll<T> {
+    type Output = Vec<T>;
+
+    /// Rather than polling each child, which would take its value out, look inside its `Shared`
+    /// directly. A child that hasn't finished saves the waker from `cx`, so whichever child
+    /// finishes next wakes us, and we look again. Only once every value is present do we take
+    /// them all.
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Vec<T>> {
+        let mut all_done = true;
+        for SpawnBlocking(shared) in &self.children {
+            let mut guard = shared.lock().unwrap();
+            if guard.value.is_none() {
+                guard.waker = Some(cx.waker().clone());
+                all_done = false;
+            }
+        }
+
+        if !all_done {
+            return Poll::Pending;
+        }
+
+        Poll::Ready(self.children.iter()
+            .map(|SpawnBlocking(shared)| shared.lock().unwrap().value.take().unwrap())
+            .collect())
+    }
+}
+
+/// The error a `Timeout` future resolves to when its deadline passes first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimedOut;
+
+/// A future that resolves to `Ok` with the output of `inner`, if it finishes before `deadline`,
+/// or to `Err(TimedOut)` if it doesn't.
+///
+/// Nothing would poll us again when the deadline passes, so on the first `Pending` poll we spawn a
+/// timer thread that waits until the deadline and then wakes whichever waker was saved most
+/// recently, much as `spawn_blocking`'s thread wakes its future. The thread waits with
+/// `recv_timeout` on a channel whose sender the `Timeout` holds, so once `inner` finishes, or the
+/// `Timeout` is dropped, the sender goes away and the thread exits early instead of sleeping out
+/// the rest of the duration.
+pub struct Timeout<F> {
+    inner: Pin<Box<F>>,
+    deadline: Instant,
+    timer: Option<Timer>,
+}
+
+/// Our side of a running timer thread. Dropping `_cancel` disconnects the channel the thread is
+/// waiting on, which tells it to exit without waking anyone.
+struct Timer {
+    waker: Arc<Mutex<Option<Waker>>>,
+    _cancel: mpsc::Sender<()>,
+}
+
+pub fn timeout<F: Future>(inner: F, duration: Duration) -> Timeout<F> {
+    Timeout {
+        inner: Box::pin(inner),
+        deadline: Instant::now() + duration,
+        timer: None,
+    }
+}
+
+impl<F: Future> Future for Timeout<F> {
+    type Output = Result<F::Output, TimedOut>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Poll::Ready(value) = self.inner.as_mut().poll(cx) {
+            self.timer = None;
+            return Poll::Ready(Ok(value));
+        }
+
+        if Instant::now() >= self.deadline {
+            self.timer = None;
+            return Poll::Ready(Err(TimedOut));
+        }
+
+        match &self.timer {
+            Some(timer) => *timer.waker.lock().unwrap() = Some(cx.waker().clone()),
+            None => {
+                let waker = Arc::new(Mutex::new(Some(cx.waker().clone())));
+                let (cancel, cancelled) = mpsc::channel::<()>();
+                let deadline = self.deadline;
+                std::thread::spawn({
+                    let waker = waker.clone();
+                    move || {
+                        let remaining = deadline.saturating_duration_since(Instant::now());
+                        if cancelled.recv_timeout(remaining) != Err(RecvTimeoutError::Timeout) {
+                            return;
+                        }
+                        let maybe_waker = waker.lock().unwrap().take();
+                        if let Some(waker) = maybe_waker {
+                            waker.wake();
+                        }
+                    }
+                });
+                self.timer = Some(Timer { waker, _cancel: cancel });
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
 use waker_fn::waker_fn;
 use futures_lite::pin;
 use crossbeam::sync::Parker;
@@ -176,3 +307,56 @@ fn unpin_example() {
     let new_home = string;
     assert_eq!(new_home, "Pinned? Not so much.");
 }
+
+#[test]
+fn test_try_spawn_blocking() {
+    let result = block_on(try_spawn_blocking(|| -> i32 { panic!("closure failed") }));
+    let payload = result.unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"closure failed"));
+
+    assert_eq!(block_on(try_spawn_blocking(|| 6 * 7)).unwrap(), 42);
+}
+
+#[test]
+fn test_join_all() {
+    let children = vec![
+        spawn_blocking(|| { std::thread::sleep(Duration::from_millis(30)); 1 }),
+        spawn_blocking(|| 2),
+        spawn_blocking(|| { std::thread::sleep(Duration::from_millis(10)); 3 }),
+    ];
+    assert_eq!(block_on(join_all(children)), vec![1, 2, 3]);
+
+    assert_eq!(block_on(join_all(Vec::<SpawnBlocking<i32>>::new())), Vec::<i32>::new());
+}
+
+#[test]
+fn test_timeout_expires() {
+    let slow = spawn_blocking(|| { std::thread::sleep(Duration::from_millis(200)); "done" });
+    let start = Instant::now();
+    assert_eq!(block_on(timeout(slow, Duration::from_millis(50))), Err(TimedOut));
+    assert!(start.elapsed() < Duration::from_millis(200));
+}
+
+#[test]
+fn test_timeout_in_time() {
+    let quick = spawn_blocking(|| { std::thread::sleep(Duration::from_millis(10)); "done" });
+    assert_eq!(block_on(timeout(quick, Duration::from_millis(1000))), Ok("done"));
+}
+
+#[test]
+fn test_timeout_stops_timer() {
+    let mut future = timeout(std::future::pending::<()>(), Duration::from_secs(60));
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+    assert!(Pin::new(&mut future).poll(&mut cx).is_pending());
+
+    // The timer thread holds the other reference to the saved waker until it exits.
+    let saved = future.timer.as_ref().unwrap().waker.clone();
+    drop(future);
+
+    let start = Instant::now();
+    while Arc::strong_count(&saved) > 1 {
+        assert!(start.elapsed() < Duration::from_secs(5), "timer thread still running");
+        std::thread::sleep(Duration::from_millis(1));
+    }
+}
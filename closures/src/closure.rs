@@ -106,6 +106,50 @@ pub(crate) fn count_selected_cities_general<F>(cities: &Vec<City>, test_fn: F) -
     count
 }
 
+/// The same count, split across threads. Each thread calls `test_fn` through a shared reference,
+/// so besides being Fn it must be Sync. Since `thread::scope` joins every thread it spawns before
+/// returning, the threads may borrow `cities` and `test_fn`, rather than needing them moved in the
+/// way `start_sorting_thread` moves its closure.
+pub(crate) fn count_selected_cities_parallel<F>(cities: &[City], test_fn: F) -> usize
+    where F: Fn(&City) -> bool + Sync
+{
+    if cities.is_empty() {
+        return 0;
+    }
+
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = cities.len().div_ceil(threads);
+    let test_fn = &test_fn;
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = cities.chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().filter(|city| test_fn(city)).count()))
+            .collect();
+        handles.into_iter()
+            .map(|handle| handle.join().expect("counting thread panicked"))
+            .sum()
+    })
+}
+
+#[test]
+fn test_count_selected_cities_parallel() {
+    let cities: Vec<City> = (0..100)
+        .map(|i| City {
+            name: format!("City {i}"),
+            population: 10_000 * i,
+            country: "Nowhere".to_string(),
+            monster_attack_risk: (i % 7) as f32 / 100.0,
+        })
+        .collect();
+
+    let risky = |city: &City| city.monster_attack_risk > 0.04;
+    assert_eq!(count_selected_cities_parallel(&cities, risky),
+               count_selected_cities_general(&cities, risky));
+    assert_eq!(count_selected_cities_parallel(&cities, has_monster_attacks),
+               count_selected_cities_general(&cities, has_monster_attacks));
+    assert_eq!(count_selected_cities_parallel(&[], has_monster_attacks), 0);
+}
+
 
 /// This generic function may be passed any closure that implements the trait Fn(): that is, closures
 /// that take no arguments and return ().
@@ -114,6 +158,42 @@ pub(crate) fn call_twice<F>(mut closure: F) where F: FnMut() {
     closure();
 }
 
+/// Call `f` until it succeeds, giving up after `attempts` calls. Return the first `Ok`, or if every
+/// attempt fails, the `Err` from the last one. Like `call_twice`, this calls its closure more than
+/// once, so it asks for FnMut: the closure is free to keep track of how many times it has run.
+/// Panics if `attempts` is zero, since then there would be no result to return.
+pub(crate) fn retry<T, E, F: FnMut() -> Result<T, E>>(mut f: F, attempts: usize) -> Result<T, E> {
+    assert!(attempts > 0, "retry needs at least one attempt");
+    for _ in 1..attempts {
+        if let Ok(value) = f() {
+            return Ok(value);
+        }
+    }
+    f()
+}
+
+#[test]
+fn test_retry_succeeds() {
+    let mut calls = 0;
+    let result = retry(|| {
+        calls += 1;
+        if calls < 3 { Err(format!("failure #{calls}")) } else { Ok(calls * 10) }
+    }, 3);
+    assert_eq!(result, Ok(30));
+    assert_eq!(calls, 3);
+}
+
+#[test]
+fn test_retry_fails() {
+    let mut calls = 0;
+    let result: Result<(), String> = retry(|| {
+        calls += 1;
+        Err(format!("failure #{calls}"))
+    }, 4);
+    assert_eq!(result, Err("failure #4".to_string()));
+    assert_eq!(calls, 4);
+}
+
 /// Closures that drop values are not allowed to have Fn. They are, quite literally, no Fn at all.
 /// They implement a less powerful trait, FnOnce, the trait of closures that can be called once.
 /// The first time you call a FnOnce closure, the closure itself is used up. It's as though the two
@@ -155,3 +235,49 @@ pub(crate) fn call_twice<F>(mut closure: F) where F: FnMut() {
 struct Dummy;
 
 
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Since a struct can hold a closure in a field of generic type, it can add behavior around the
+/// calls. `Memoize` remembers what `f` returned for each argument, and hands back the saved result
+/// when the same argument comes around again, rather than calling `f` a second time.
+/// `call` takes `&mut self`, both to update the cache and so that `f` may be an FnMut closure.
+pub(crate) struct Memoize<A, B, F> {
+    f: F,
+    cache: HashMap<A, B>,
+}
+
+impl<A, B, F> Memoize<A, B, F> {
+    pub(crate) fn new(f: F) -> Memoize<A, B, F> {
+        Memoize { f, cache: HashMap::new() }
+    }
+
+    pub(crate) fn call(&mut self, arg: A) -> B
+        where A: Eq + Hash + Clone, B: Clone, F: FnMut(A) -> B
+    {
+        if let Some(result) = self.cache.get(&arg) {
+            return result.clone();
+        }
+        let result = (self.f)(arg.clone());
+        self.cache.insert(arg, result.clone());
+        result
+    }
+}
+
+#[test]
+fn test_memoize() {
+    use std::cell::Cell;
+
+    let calls = Cell::new(0);
+    let mut square = Memoize::new(|n: u64| {
+        calls.set(calls.get() + 1);
+        n * n
+    });
+
+    assert_eq!(square.call(4), 16);
+    assert_eq!(square.call(5), 25);
+    assert_eq!(square.call(4), 16);
+    assert_eq!(square.call(4), 16);
+    assert_eq!(square.call(5), 25);
+    assert_eq!(calls.get(), 2);
+}
@@ -31,6 +31,9 @@ fn main() {
     let n = closure::count_selected_cities_general(&cities, |city| city.monster_attack_risk > limit);
     println!("{n}");
 
+    let m = closure::count_selected_cities_parallel(&cities, |city| city.monster_attack_risk > limit);
+    assert_eq!(m, n);
+
     let mut i = 0;
     let incr = || {
         i += 1;
@@ -41,6 +44,13 @@ fn main() {
 
     assert_eq!(i, 2);
 
+    let mut tries = 0;
+    let outcome = closure::retry(|| {
+        tries += 1;
+        if tries < 2 { Err("busy") } else { Ok(tries) }
+    }, 3);
+    assert_eq!(outcome, Ok(2));
+
     let y = 10;
     let add_y = |x| x + y;
     let copy_of_add_y = add_y;  //This closure is `Copy`
@@ -60,6 +70,10 @@ fn main() {
     greet.clone()("Alfred");
     greet.clone()("Bruce");
 
+    let mut lengths = closure::Memoize::new(|s: &str| s.chars().count());
+    assert_eq!(lengths.call("Chennai"), 7);
+    assert_eq!(lengths.call("Chennai"), 7);
+
     let mut router = callbacks::BasicRouter::new();
     router.add_route("/", |_| callbacks::get_form_response());
     router.add_route("/gcd", |req| callbacks::get_gcd_response(req));
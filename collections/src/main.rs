@@ -19,6 +19,43 @@ fn retain() {
 
     assert_eq!(&byte_vec, b"Misp");
 
+    let mut byte_vec = b"Missssssssissippi".to_vec();
+    dedup_consecutive(&mut byte_vec);
+    assert_eq!(&byte_vec, b"Misisipi");
+}
+
+/// What Vec::dedup does, by hand: remove consecutive repeated elements, keeping the first of each
+/// run. Elements that are kept are swapped down to the front of the vector, past the duplicates,
+/// which are then cut off the end all at once.
+fn dedup_consecutive<T: PartialEq>(v: &mut Vec<T>) {
+    if v.is_empty() {
+        return;
+    }
+
+    // `v[..kept]` holds the elements we've decided to keep so far.
+    let mut kept = 1;
+    for i in 1..v.len() {
+        if v[i] != v[kept - 1] {
+            v.swap(i, kept);
+            kept += 1;
+        }
+    }
+    v.truncate(kept);
+}
+
+#[test]
+fn test_dedup_consecutive() {
+    let mut v = vec![1, 1, 2, 2, 2, 1];
+    dedup_consecutive(&mut v);
+    assert_eq!(v, [1, 2, 1]);
+
+    let mut v: Vec<i32> = vec![];
+    dedup_consecutive(&mut v);
+    assert!(v.is_empty());
+
+    let mut v = vec!["a", "b", "c", "a"];
+    dedup_consecutive(&mut v);
+    assert_eq!(v, ["a", "b", "c", "a"]);
 }
 
 /// Rust has several methods that can borrow mut references to two or more parts of an array, slice,
@@ -40,6 +77,40 @@ fn heap() {
         }
     };
 
+    // The 8 on top was overwritten with a 5.
+    assert_eq!(top_k(&heap.into_vec(), 3), vec![6, 5, 5]);
+}
+
+use std::cmp::Reverse;
+
+/// Return the `k` largest of `items`, largest first. Wrapping items in `Reverse` turns the max-heap
+/// into a min-heap, whose top is the smallest of the `k` largest seen so far: anything bigger
+/// replaces it through `peek_mut`, which re-sifts the heap when the `PeekMut` guard is dropped.
+/// This keeps the heap at `k` elements rather than heaping up all of `items`.
+fn top_k<T: Ord + Clone>(items: &[T], k: usize) -> Vec<T> {
+    let mut heap = BinaryHeap::with_capacity(k);
+    for item in items {
+        if heap.len() < k {
+            heap.push(Reverse(item.clone()));
+        } else if let Some(mut smallest) = heap.peek_mut() {
+            if *item > smallest.0 {
+                *smallest = Reverse(item.clone());
+            }
+        }
+    }
+
+    // Sorting `Reverse` values in ascending order puts the items themselves in descending order.
+    heap.into_sorted_vec().into_iter().map(|Reverse(item)| item).collect()
+}
+
+#[test]
+fn test_top_k() {
+    assert_eq!(top_k(&[5, 1, 9, 3, 7], 3), vec![9, 7, 5]);
+    assert_eq!(top_k(&[5, 1, 9, 3, 7], 0), Vec::<i32>::new());
+    assert_eq!(top_k(&[5, 1, 9, 3, 7], 5), vec![9, 7, 5, 3, 1]);
+    assert_eq!(top_k(&[5, 1, 9, 3, 7], 10), vec![9, 7, 5, 3, 1]);
+    assert_eq!(top_k(&[4, 4, 1, 4], 2), vec![4, 4]);
+    assert_eq!(top_k(&["pear", "apple", "quince"], 1), vec!["quince"]);
 }
 
 
@@ -73,11 +144,28 @@ fn entries() {
             .or_insert(1);
     }
 
-    for (key, value) in &word_frequency {
+    for (key, value) in ranked_by_frequency(&word_frequency) {
         println!("{key}: {value}");
     }
 }
 
+/// Iterating over a HashMap visits its entries in no particular order. To list word counts
+/// predictably, collect them into a vector and sort it: most frequent first, and words with equal
+/// counts in alphabetical order.
+fn ranked_by_frequency<'a>(freq: &'a HashMap<&'a str, u32>) -> Vec<(&'a str, u32)> {
+    let mut ranked: Vec<(&str, u32)> = freq.iter().map(|(&word, &count)| (word, count)).collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    ranked
+}
+
+#[test]
+fn test_ranked_by_frequency() {
+    let freq = HashMap::from([("the", 4), ("plums", 2), ("icebox", 1), ("eaten", 2), ("cold", 1)]);
+    assert_eq!(ranked_by_frequency(&freq),
+               vec![("the", 4), ("eaten", 2), ("plums", 2), ("cold", 1), ("icebox", 1)]);
+    assert_eq!(ranked_by_frequency(&HashMap::new()), vec![]);
+}
+
 use enums_and_patterns::enums::RoughTime;
 /// Hashing
 /// std::hash::Hash is the standard library trait for hashable types. HashMap keys and HashSet
@@ -89,7 +177,7 @@ use enums_and_patterns::enums::RoughTime;
 /// with the same characters.
 /// If you implement PartialEq by hand for a type, you should also implement Hash by hand.
 struct Artifact {
-    id: u32,
+    id: ArtifactId,
     name: String,
     cultures: Vec<Culture>,
     date: RoughTime
@@ -114,3 +202,42 @@ impl Hash for Artifact {
         self.id.hash(hasher);
     }
 }
+
+/// An artifact's identifier. Artifacts compare and hash by `id` alone, so an `Artifact` can lend
+/// out its `ArtifactId` through `Borrow`: the two are guaranteed to hash and compare the same way,
+/// which is just the promise `Borrow` requires. Sets and maps of artifacts can then be searched by
+/// id, without having to build a whole `Artifact` to look for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct ArtifactId(u32);
+
+use std::borrow::Borrow;
+
+impl Borrow<ArtifactId> for Artifact {
+    fn borrow(&self) -> &ArtifactId {
+        &self.id
+    }
+}
+
+#[test]
+fn test_artifact_lookup_by_id() {
+    use enums_and_patterns::enums::TimeUnit;
+
+    let mut collection = HashSet::new();
+    collection.insert(Artifact {
+        id: ArtifactId(1519),
+        name: "Sun Stone".to_string(),
+        cultures: vec![Culture::Aztec],
+        date: RoughTime::InThePast(TimeUnit::Years, 500),
+    });
+    collection.insert(Artifact {
+        id: ArtifactId(1450),
+        name: "Quipu".to_string(),
+        cultures: vec![Culture::Inca],
+        date: RoughTime::InThePast(TimeUnit::Years, 570),
+    });
+
+    let found = collection.get(&ArtifactId(1519)).expect("artifact 1519 should be present");
+    assert_eq!(found.name, "Sun Stone");
+    assert!(collection.contains(&ArtifactId(1450)));
+    assert!(!collection.contains(&ArtifactId(1)));
+}
@@ -81,20 +81,79 @@ fn start_file_indexing_thread(texts: mpsc::Receiver<String>)
 }
 
 
+/// Merge the small per-document indexes into bigger ones, each holding up to about
+/// `InMemoryIndex::REASONABLE_SIZE` words, which is as much as we want to hold in memory at a time.
 fn start_in_memory_merge_thread(file_indexes: mpsc::Receiver<InMemoryIndex>)
     -> (mpsc::Receiver<InMemoryIndex>, thread::JoinHandle<()>) {
+    start_in_memory_merge_thread_with_limit(file_indexes, InMemoryIndex::REASONABLE_SIZE)
+}
+
+/// Like `start_in_memory_merge_thread`, but send each merged index on as soon as it holds more than
+/// `word_limit` words. Whatever has accumulated when the input closes is sent last, unless it's
+/// empty.
+fn start_in_memory_merge_thread_with_limit(file_indexes: mpsc::Receiver<InMemoryIndex>,
+                                           word_limit: usize)
+    -> (mpsc::Receiver<InMemoryIndex>, thread::JoinHandle<()>) {
     let (sender, receiver) = mpsc::channel();
 
     let handle = thread::spawn(move || {
-        //dummy implementation for merge now.
-        let new_index = InMemoryIndex::new();
-        if sender.send(new_index).is_err() {
+        let mut accumulated_index = InMemoryIndex::new();
+        for fi in file_indexes {
+            accumulated_index.merge(fi);
+            if accumulated_index.word_count > word_limit {
+                if sender.send(accumulated_index).is_err() {
+                    return;
+                }
+                accumulated_index = InMemoryIndex::new();
+            }
+        }
 
+        if !accumulated_index.is_empty() {
+            let _ = sender.send(accumulated_index);
         }
     });
     (receiver, handle)
 }
 
+#[test]
+fn test_in_memory_merge() {
+    let documents = ["the quick brown fox", "jumps over", "the lazy dog"];
+    let feed = || {
+        let (sender, receiver) = mpsc::channel();
+        for (id, text) in documents.iter().enumerate() {
+            sender.send(InMemoryIndex::from_single_document(id, text.to_string())).unwrap();
+        }
+        receiver
+    };
+    let mut all_terms: Vec<&str> = documents.iter().flat_map(|text| text.split(' ')).collect();
+    all_terms.sort();
+    all_terms.dedup();
+
+    // With a generous limit, everything is merged into a single index.
+    let (merged, handle) = start_in_memory_merge_thread(feed());
+    let merged: Vec<InMemoryIndex> = merged.into_iter().collect();
+    handle.join().unwrap();
+    assert_eq!(merged.len(), 1);
+    assert_eq!(merged[0].word_count, 9);
+    let mut terms: Vec<&str> = merged[0].map.keys().map(String::as_str).collect();
+    terms.sort();
+    assert_eq!(terms, all_terms);
+    assert_eq!(merged[0].map["the"].len(), 2);
+
+    // With a small limit, an index is sent on whenever it passes the limit, and the remainder last.
+    let (merged, handle) = start_in_memory_merge_thread_with_limit(feed(), 3);
+    let merged: Vec<InMemoryIndex> = merged.into_iter().collect();
+    handle.join().unwrap();
+    let word_counts: Vec<usize> = merged.iter().map(|index| index.word_count).collect();
+    assert_eq!(word_counts, vec![4, 5]);
+    let mut terms: Vec<&str> = merged.iter()
+        .flat_map(|index| index.map.keys().map(String::as_str))
+        .collect();
+    terms.sort();
+    terms.dedup();
+    assert_eq!(terms, all_terms);
+}
+
 fn start_index_writer_thread(big_indexes: mpsc::Receiver<InMemoryIndex>, output_dir: &Path)
     -> (mpsc::Receiver<PathBuf>, thread::JoinHandle<io::Result<()>>) {
     let (sender, receiver) = mpsc::channel();
@@ -141,6 +200,41 @@ fn run_pipeline(documents: Vec<PathBuf>, output_dir: PathBuf) -> io::Result<()>
 }
 
 
+/// Fan in: forward everything received on any of `receivers` to a single channel, so that one
+/// consumer can read from many producers. Each input gets a forwarding thread of its own, sending
+/// on a clone of the output `Sender`, so values from different inputs may arrive interleaved in any
+/// order. The output channel closes once every input has closed.
+fn merge<T: Send + 'static>(receivers: Vec<mpsc::Receiver<T>>) -> mpsc::Receiver<T> {
+    let (sender, receiver) = mpsc::channel();
+
+    for input in receivers {
+        let sender = sender.clone();
+        thread::spawn(move || {
+            for value in input {
+                if sender.send(value).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    receiver
+}
+
+#[test]
+fn test_merge() {
+    let mut receivers = Vec::new();
+    for value in ["one", "two", "three"] {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || sender.send(value).unwrap());
+        receivers.push(receiver);
+    }
+
+    let mut merged: Vec<&str> = merge(receivers).into_iter().collect();
+    merged.sort();
+    assert_eq!(merged, vec!["one", "three", "two"]);
+}
+
 /// Channel Features and Performance
 /// The mpsc part of std::sync::mpsc stands for multiproducer, single-consumer, a terse description
 /// of the kind of communication Rust's channels provide.
@@ -207,6 +301,19 @@ pub trait OffThreadExt: Iterator {
     /// Transform this iterator into an off-thread iterator: the `next()` calls happen on a separate
     /// worker thread, so the iterator and the body of your loop run concurrently.
     fn off_thread(self) -> mpsc::IntoIter<Self::Item>;
+
+    /// Like `off_thread`, but let the worker thread get at most `capacity` items ahead of the
+    /// consumer before it blocks. A smaller capacity applies backpressure sooner, and holds fewer
+    /// items in memory; a larger one lets the worker run further ahead of a consumer that's slow
+    /// only now and then.
+    fn off_thread_bounded(self, capacity: usize) -> mpsc::IntoIter<Self::Item>;
+
+    /// Apply `f` to every item on a pool of `workers` threads, handing items out to them in turn.
+    /// The results come back in whatever order the workers finish them, which need not be the order
+    /// of the items they came from.
+    fn par_map<B, F>(self, workers: usize, f: F) -> mpsc::IntoIter<B>
+        where F: Fn(Self::Item) -> B + Send + Sync + Clone + 'static,
+              B: Send + 'static;
 }
 
 impl<T> OffThreadExt for T
@@ -214,8 +321,12 @@ impl<T> OffThreadExt for T
           T::Item: Send + 'static
 {
     fn off_thread(self) -> mpsc::IntoIter<Self::Item> {
+        self.off_thread_bounded(1024)
+    }
+
+    fn off_thread_bounded(self, capacity: usize) -> mpsc::IntoIter<Self::Item> {
         // Create a channel to transfer items from the worker thread.
-        let (sender, receiver) = mpsc::sync_channel(1024);
+        let (sender, receiver) = mpsc::sync_channel(capacity);
 
         thread::spawn(move || {
             for item in self {
@@ -228,6 +339,71 @@ impl<T> OffThreadExt for T
         //Return an iterator that pulls values from the channel.
         receiver.into_iter()
     }
+
+    fn par_map<B, F>(self, workers: usize, f: F) -> mpsc::IntoIter<B>
+        where F: Fn(Self::Item) -> B + Send + Sync + Clone + 'static,
+              B: Send + 'static
+    {
+        let (result_sender, result_receiver) = mpsc::sync_channel(1024);
+
+        // Each worker gets a channel of its own, and sends its results to the shared output.
+        let mut item_senders = Vec::new();
+        for _ in 0..workers.max(1) {
+            let (item_sender, item_receiver) = mpsc::sync_channel(1024);
+            item_senders.push(item_sender);
+            let result_sender = result_sender.clone();
+            let f = f.clone();
+            thread::spawn(move || {
+                for item in item_receiver {
+                    if result_sender.send(f(item)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        // Deal the items out round-robin. When this thread finishes, dropping `item_senders` lets
+        // each worker's loop end; once they've all exited, the output channel closes.
+        thread::spawn(move || {
+            for (item, sender) in self.zip(item_senders.iter().cycle()) {
+                if sender.send(item).is_err() {
+                    break;
+                }
+            }
+        });
+
+        result_receiver.into_iter()
+    }
+}
+
+#[test]
+fn test_off_thread_bounded() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    // Count how many items the worker has pulled from the source iterator.
+    let produced = Arc::new(AtomicUsize::new(0));
+    let counter = produced.clone();
+    let mut items = (0..10)
+        .inspect(move |_| { counter.fetch_add(1, Ordering::SeqCst); })
+        .off_thread_bounded(1);
+
+    // With room for one item in the channel, the worker can pull one item to fill it and a second
+    // that it is blocked trying to send, but no more.
+    thread::sleep(Duration::from_millis(100));
+    assert!(produced.load(Ordering::SeqCst) <= 2);
+
+    assert_eq!(items.next(), Some(0));
+    assert_eq!(items.collect::<Vec<_>>(), (1..10).collect::<Vec<_>>());
+    assert_eq!(produced.load(Ordering::SeqCst), 10);
+}
+
+#[test]
+fn test_par_map() {
+    let mut squares: Vec<u64> = (0..100_u64).par_map(4, |n| n * n).collect();
+    squares.sort();
+    assert_eq!(squares, (0..100_u64).map(|n| n * n).collect::<Vec<_>>());
 }
 
 /// Channels can also be used for cases where one thread sends a request to another thread and needs
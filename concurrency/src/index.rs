@@ -99,8 +99,10 @@ impl InMemoryIndex {
     /// True if this index is large enough that we should dump it to disk rather than keep adding
     /// more data to it.
     pub fn is_large(&self) -> bool {
-        /// This depends on how much memory your computer has, of-course.
-        const REASONABLE_SIZE : usize = 100_000_000;
-        self.word_count > REASONABLE_SIZE
+        self.word_count > Self::REASONABLE_SIZE
     }
+
+    /// The number of words beyond which an index counts as large. This depends on how much memory
+    /// your computer has, of-course.
+    pub const REASONABLE_SIZE : usize = 100_000_000;
 }
@@ -3,7 +3,7 @@ const GAME_SIZE: usize=8;
 
 type WaitingList = Vec<PlayerId>;
 
-use std::sync::Mutex;
+use std::sync::{Mutex, MutexGuard};
 
 struct FernEmpireApp {
     id: PlayerId,
@@ -24,7 +24,7 @@ impl FernEmpireApp {
     /// Add a player to the waiting list for the next game. Start a new game immediately if enough
     /// players are waiting.
     fn join_waiting_list(&self, player: PlayerId) {
-        let mut guard = self.waiting_list.lock().unwrap();
+        let mut guard = lock_or_recover(&self.waiting_list);
 
         guard.push(player);
         if guard.len() == GAME_SIZE {
@@ -39,8 +39,38 @@ impl FernEmpireApp {
     }
 }
 
+/// Lock `m`, even if it's poisoned. A waiting list that a panicking thread left behind is still a
+/// list of players: at worst, the last one may or may not have been added. That's better than
+/// bringing down every thread that later wants to join a game, so we warn and carry on.
+fn lock_or_recover<T>(m: &Mutex<T>) -> MutexGuard<'_, T> {
+    match m.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => {
+            eprintln!("warning: recovering from a poisoned mutex");
+            poisoned.into_inner()
+        }
+    }
+}
+
+#[test]
+fn test_join_waiting_list_after_poisoning() {
+    let app = Arc::new(FernEmpireApp { id: 0, waiting_list: Mutex::new(vec![1, 2]) });
+
+    let poisoner = app.clone();
+    let result = std::thread::spawn(move || {
+        let _guard = poisoner.waiting_list.lock().unwrap();
+        panic!("panic while holding the waiting list lock");
+    }).join();
+    assert!(result.is_err());
+    assert!(app.waiting_list.is_poisoned());
+
+    app.join_waiting_list(3);
+    assert_eq!(*lock_or_recover(&app.waiting_list), vec![1, 2, 3]);
+}
+
 use std::sync::{Arc};
 use std::sync::mpsc::{channel, Sender, Receiver};
+use std::time::Duration;
 /// Safe Rust code cannot trigger a data race, a specific kind of bug where multiple thread read and
 /// write the same memory concurrently, producing meaningless results.
 /// However, threads that use mutexes are subject to some other problems that Rust doesn't fix for you:
@@ -88,9 +118,134 @@ impl<T> Iterator for SharedReceiver<T> {
 
 }
 
+impl<T> SharedReceiver<T> {
+    /// Take every value that's already waiting in the channel, without blocking for more. The
+    /// mutex is locked once for the whole batch, rather than once per value as `next` does.
+    pub fn try_drain(&self) -> Vec<T> {
+        let guard = self.0.lock().unwrap();
+        guard.try_iter().collect()
+    }
+
+    /// Like `next`, but give up and return `None` if no value arrives within `dur`. `None` also
+    /// means every sender has been dropped.
+    ///
+    /// This holds the mutex while it waits, just as `next` does, so other consumers calling any of
+    /// these methods queue up behind it for as long as `dur`. That can't deadlock, since the wait is
+    /// bounded, but it does mean another consumer's own timeout can expire while it's still waiting
+    /// for the lock.
+    pub fn recv_timeout(&self, dur: Duration) -> Option<T> {
+        let guard = self.0.lock().unwrap();
+        guard.recv_timeout(dur).ok()
+    }
+}
+
+#[test]
+fn test_recv_timeout() {
+    use std::time::Instant;
+
+    let (sender, receiver) = shared_channel::<i32>();
+    let start = Instant::now();
+    assert_eq!(receiver.recv_timeout(Duration::from_millis(50)), None);
+    let elapsed = start.elapsed();
+    assert!(elapsed >= Duration::from_millis(50));
+    assert!(elapsed < Duration::from_secs(5));
+
+    sender.send(7).unwrap();
+    assert_eq!(receiver.recv_timeout(Duration::from_millis(50)), Some(7));
+}
+
+#[test]
+fn test_try_drain() {
+    let (sender, receiver) = shared_channel();
+    for i in 1..=3 {
+        sender.send(i).unwrap();
+    }
+
+    assert_eq!(receiver.try_drain(), vec![1, 2, 3]);
+    assert_eq!(receiver.try_drain(), Vec::<i32>::new());
+}
+
 /// Create a new channel whose receiver can be shared across threads. This returns a sender and α
 /// receiver, just like the stdlib's `channel()`, and sometimes works as a drop-in replacement.
 pub fn shared_channel<T>() -> (Sender<T>, SharedReceiver<T>) {
     let (sender, receiver) = channel();
     (sender, SharedReceiver(Arc::new(Mutex::new(receiver))))
 }
+
+
+use std::collections::VecDeque;
+
+/// A work queue shared between threads. Where a SharedReceiver hands out one value at a time, a
+/// WorkQueue lets an idle worker grab a whole batch at once, which helps balance the load when
+/// some items take much longer than others. Cloning a WorkQueue gives another handle to the same
+/// queue.
+#[derive(Clone)]
+pub struct WorkQueue<T>(Arc<Mutex<VecDeque<T>>>);
+
+impl<T> WorkQueue<T> {
+    pub fn new() -> WorkQueue<T> {
+        WorkQueue(Arc::new(Mutex::new(VecDeque::new())))
+    }
+
+    /// Add an item to the back of the queue.
+    pub fn push(&self, item: T) {
+        self.0.lock().unwrap().push_back(item);
+    }
+
+    /// Take the item at the front of the queue, if there is one.
+    pub fn pop(&self) -> Option<T> {
+        self.0.lock().unwrap().pop_front()
+    }
+
+    /// Take up to `n` items from the back of the queue, the end farthest from the one `pop`
+    /// works on. The items come back in queue order.
+    pub fn steal_batch(&self, n: usize) -> Vec<T> {
+        let mut guard = self.0.lock().unwrap();
+        let at = guard.len().saturating_sub(n);
+        guard.split_off(at).into()
+    }
+}
+
+#[test]
+fn test_work_queue_drained_by_workers() {
+    use std::collections::HashSet;
+    use std::thread;
+
+    let queue = WorkQueue::new();
+    for i in 0..1000 {
+        queue.push(i);
+    }
+
+    let workers: Vec<_> = (0..4).map(|worker| {
+        let queue = queue.clone();
+        thread::spawn(move || {
+            let mut taken = Vec::new();
+            loop {
+                // Alternate between taking single items and stealing batches.
+                if worker % 2 == 0 {
+                    match queue.pop() {
+                        Some(item) => taken.push(item),
+                        None => break,
+                    }
+                } else {
+                    let batch = queue.steal_batch(7);
+                    if batch.is_empty() {
+                        break;
+                    }
+                    taken.extend(batch);
+                }
+            }
+            taken
+        })
+    }).collect();
+
+    let mut all = Vec::new();
+    for handle in workers {
+        all.extend(handle.join().unwrap());
+    }
+
+    assert_eq!(all.len(), 1000);
+    let unique: HashSet<i32> = all.into_iter().collect();
+    assert_eq!(unique, (0..1000).collect());
+    assert!(queue.pop().is_none());
+}
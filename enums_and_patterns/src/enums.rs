@@ -75,6 +75,64 @@ pub(crate) enum Shape {
     Cuboid {corner1: Point3d, corner2: Point3d},
 }
 
+/// Methods on an enum with data usually begin with a match, since each variant needs its own
+/// formula. Both measurements are in the cube and square of whatever unit the coordinates use.
+impl Shape {
+    pub fn volume(&self) -> f64 {
+        use std::f64::consts::PI;
+        match self {
+            Shape::Sphere { radius, .. } => {
+                let r = *radius as f64;
+                4.0 / 3.0 * PI * r * r * r
+            }
+            Shape::Cuboid { corner1, corner2 } => {
+                let (dx, dy, dz) = corner1.extents_to(corner2);
+                dx * dy * dz
+            }
+        }
+    }
+
+    pub fn surface_area(&self) -> f64 {
+        use std::f64::consts::PI;
+        match self {
+            Shape::Sphere { radius, .. } => {
+                let r = *radius as f64;
+                4.0 * PI * r * r
+            }
+            Shape::Cuboid { corner1, corner2 } => {
+                let (dx, dy, dz) = corner1.extents_to(corner2);
+                2.0 * (dx * dy + dy * dz + dz * dx)
+            }
+        }
+    }
+}
+
+impl Point3d {
+    /// The lengths of the edges of the axis-aligned box with `self` and `other` as opposite corners.
+    fn extents_to(&self, other: &Point3d) -> (f64, f64, f64) {
+        ((self.x - other.x).abs() as f64,
+         (self.y - other.y).abs() as f64,
+         (self.z - other.z).abs() as f64)
+    }
+}
+
+#[test]
+fn test_sphere_measurements() {
+    let unit_sphere = Shape::Sphere { center: Point3d::ORIGIN, radius: 1.0 };
+    assert!((unit_sphere.volume() - 4.18879).abs() < 1e-5);
+    assert!((unit_sphere.surface_area() - 12.56637).abs() < 1e-5);
+}
+
+#[test]
+fn test_cuboid_measurements() {
+    let cuboid = Shape::Cuboid {
+        corner1: Point3d { x: 1.0, y: 2.0, z: 3.0 },
+        corner2: Point3d::ORIGIN,
+    };
+    assert!((cuboid.volume() - 6.0).abs() < 1e-9);
+    assert!((cuboid.surface_area() - 22.0).abs() < 1e-9);
+}
+
 use std:: collections::HashMap;
 
 /// In memory, enums with data are stored as a small integer tag, plus enough memory to hold all the
@@ -115,3 +173,120 @@ pub struct TreeNode<T> {
     pub left: BinaryTree<T>,
     pub right: BinaryTree<T>,
 }
+
+/// The order in which a depth-first walk over a BinaryTree visits each node relative to its
+/// subtrees.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TraversalOrder {
+    PreOrder, InOrder, PostOrder,
+}
+
+impl<T> BinaryTree<T> {
+    /// Walk the tree depth-first in the given order. The walk keeps its own stack rather than
+    /// recursing, so it can hand out one element per call to next.
+    pub fn iter_order(&self, order: TraversalOrder) -> impl Iterator<Item=&T> {
+        let mut iter = DepthFirstIter { order, stack: Vec::new() };
+        iter.push_tree(self);
+        iter
+    }
+
+    /// Walk the tree in order: each node's left subtree, then the node, then its right subtree.
+    /// For a tree built with `add`, this produces the elements in sorted order.
+    pub fn iter(&self) -> impl Iterator<Item=&T> {
+        self.iter_order(TraversalOrder::InOrder)
+    }
+}
+
+/// Each entry on the stack is a node, plus whether its subtrees have already been scheduled. A
+/// node whose subtrees are scheduled is ready to produce its element.
+struct DepthFirstIter<'a, T> {
+    order: TraversalOrder,
+    stack: Vec<(&'a TreeNode<T>, bool)>,
+}
+
+impl<'a, T> DepthFirstIter<'a, T> {
+    fn push_tree(&mut self, tree: &'a BinaryTree<T>) {
+        if let BinaryTree::NonEmpty(ref node) = *tree {
+            self.stack.push((node, false));
+        }
+    }
+}
+
+impl<'a, T> Iterator for DepthFirstIter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<&'a T> {
+        while let Some((node, expanded)) = self.stack.pop() {
+            if expanded {
+                return Some(&node.element);
+            }
+
+            // The stack is last-in, first-out, so push in the reverse of the visiting order.
+            match self.order {
+                TraversalOrder::PreOrder => {
+                    self.push_tree(&node.right);
+                    self.push_tree(&node.left);
+                    self.stack.push((node, true));
+                }
+                TraversalOrder::InOrder => {
+                    self.push_tree(&node.right);
+                    self.stack.push((node, true));
+                    self.push_tree(&node.left);
+                }
+                TraversalOrder::PostOrder => {
+                    self.stack.push((node, true));
+                    self.push_tree(&node.right);
+                    self.push_tree(&node.left);
+                }
+            }
+        }
+        None
+    }
+}
+
+#[test]
+fn test_iter_order() {
+    fn leaf(element: &'static str) -> BinaryTree<&'static str> {
+        BinaryTree::NonEmpty(Box::new(TreeNode {
+            element, left: BinaryTree::Empty, right: BinaryTree::Empty,
+        }))
+    }
+
+    //       Mars
+    //      /    \
+    //  Jupiter  Venus
+    //            /
+    //        Saturn
+    let tree = BinaryTree::NonEmpty(Box::new(TreeNode {
+        element: "Mars",
+        left: leaf("Jupiter"),
+        right: BinaryTree::NonEmpty(Box::new(TreeNode {
+            element: "Venus",
+            left: leaf("Saturn"),
+            right: BinaryTree::Empty,
+        })),
+    }));
+
+    let walk = |order| tree.iter_order(order).cloned().collect::<Vec<_>>();
+    assert_eq!(walk(TraversalOrder::PreOrder), vec!["Mars", "Jupiter", "Venus", "Saturn"]);
+    assert_eq!(walk(TraversalOrder::InOrder), vec!["Jupiter", "Mars", "Saturn", "Venus"]);
+    assert_eq!(walk(TraversalOrder::PostOrder), vec!["Jupiter", "Saturn", "Venus", "Mars"]);
+
+    let empty: BinaryTree<i32> = BinaryTree::Empty;
+    assert_eq!(empty.iter_order(TraversalOrder::InOrder).next(), None);
+}
+
+#[test]
+fn test_iter_sorted() {
+    let mut tree = BinaryTree::Empty;
+    tree.add("Mercury");
+    tree.add("Venus");
+    tree.add("Earth");
+    tree.add("Mars");
+    tree.add("Jupiter");
+
+    assert_eq!(tree.iter().cloned().collect::<Vec<_>>(),
+               vec!["Earth", "Jupiter", "Mars", "Mercury", "Venus"]);
+
+    let empty: BinaryTree<&str> = BinaryTree::Empty;
+    assert_eq!(empty.iter().count(), 0);
+}
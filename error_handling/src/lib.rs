@@ -0,0 +1,36 @@
+//! The error types defined here are shared with other crates in this workspace, such as the JSON
+//! parser in the macros crate, so they live in a library alongside the examples in main.rs.
+
+use std::fmt;
+
+/// An error found while parsing JSON, with the position in the input where it was found. Lines and
+/// columns count from 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonError {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl JsonError {
+    pub fn new(message: impl Into<String>, line: usize, column: usize) -> JsonError {
+        JsonError { message: message.into(), line, column }
+    }
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+
+        write!(f, "{} ({}:{})", self.message, self.line, self.column)
+    }
+}
+
+//Errors should implement the std::error::Error trait, but the default definitions for the Error
+//methods are fine.
+impl std::error::Error for JsonError {}
+
+#[test]
+fn test_json_error_display() {
+    let error = JsonError::new("expected ']' at end of array", 3, 17);
+    assert_eq!(error.to_string(), "expected ']' at end of array (3:17)");
+}
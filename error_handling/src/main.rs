@@ -83,33 +83,13 @@ let weather = match get_weather(hometown) {
 unwrap a value and return early in case of None.
  */
 
-#[derive(Debug, Clone)]
-pub struct JsonError {
-    pub message: String,
-    pub line: usize,
-    pub column: usize,
-}
-
-use std::fmt;
-
-impl fmt::Display for JsonError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-
-        write!(f, "{} ({}:{}", self.message, self.line, self.column)
-    }
-}
-
-//Errors should implement the std::error::Error trait, but the default definitions for the Error
-//methods are fine.
-impl std::error::Error for JsonError {}
+// Custom error types are ordinary structs or enums. `JsonError` is defined in this crate's library,
+// src/lib.rs, so that other crates can use it too.
+use error_handling::JsonError;
 
 fn json_error() -> Result<(), JsonError> {
     let current_line = 0;
     let current_column = 0;
 
-    Err(JsonError{
-        message: "expected ']' at end of array".to_string(),
-        line: current_line,
-        column: current_column
-    })
+    Err(JsonError::new("expected ']' at end of array", current_line, current_column))
 }
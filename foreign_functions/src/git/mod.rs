@@ -143,6 +143,54 @@ pub struct Oid {
     pub raw: raw::git_oid
 }
 
+/// Display an `Oid` as Git does: forty lowercase hexadecimal digits.
+impl fmt::Display for Oid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        // `git_oid_tostr` always null-terminates what it writes, so leave room for the terminator.
+        let mut buffer = [0 as c_char; raw::GIT_OID_HEXSZ + 1];
+        let hex = unsafe {
+            raw::git_oid_tostr(buffer.as_mut_ptr(), buffer.len(), &self.raw);
+            CStr::from_ptr(buffer.as_ptr())
+        };
+        f.write_str(&hex.to_string_lossy())
+    }
+}
+
+/// Parse an `Oid` from its forty-digit hexadecimal form.
+impl std::str::FromStr for Oid {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Oid> {
+        // `git_oid_fromstr` reads exactly forty characters, whatever the string's actual length.
+        if s.len() != raw::GIT_OID_HEXSZ {
+            return Err(format!("object id '{s}' is not {} hex digits", raw::GIT_OID_HEXSZ).into());
+        }
+        Repository::ensure_initialized();
+
+        let s = CString::new(s)?;
+        unsafe {
+            let mut oid = mem::MaybeUninit::uninit();
+            check(raw::git_oid_fromstr(oid.as_mut_ptr(), s.as_ptr()))?;
+            Ok(Oid {raw: oid.assume_init()})
+        }
+    }
+}
+
+#[test]
+fn test_oid_round_trip() {
+    let hex = "5ba9bd2d1e0a3c8a47e9ee4c1d7b4d9c26d2f0aa";
+    let oid: Oid = hex.parse().unwrap();
+    assert_eq!(oid.raw.id[0], 0x5b);
+    assert_eq!(oid.to_string(), hex);
+
+    // Uppercase digits are accepted, but Display always produces lowercase.
+    let oid: Oid = hex.to_uppercase().parse().unwrap();
+    assert_eq!(oid.to_string(), hex);
+
+    assert!("5ba9bd2d".parse::<Oid>().is_err());
+    assert!("5ba9bd2d1e0a3c8a47e9ee4c1d7b4d9c26d2f0zz".parse::<Oid>().is_err());
+}
+
 use std::os::raw::c_char;
 
 impl Repository {
@@ -158,6 +206,39 @@ impl Repository {
             Ok(Oid {raw: oid})
         }
     }
+
+    /// Return the names of all the references in the repository: branches, like
+    /// `refs/heads/master`, tags, remote-tracking branches and so on.
+    pub fn references(&self) -> Result<Vec<String>> {
+        let mut iter = ptr::null_mut();
+        unsafe {
+            check(raw::git_reference_iterator_new(&mut iter, self.raw))?;
+            // Collect the names before freeing the iterator, even if one of them fails.
+            let names = Self::reference_names(iter);
+            raw::git_reference_iterator_free(iter);
+            names
+        }
+    }
+
+    /// Safety: `iter` must point to a live `git_reference_iterator`.
+    unsafe fn reference_names(iter: *mut raw::git_reference_iterator) -> Result<Vec<String>> {
+        let mut names = Vec::new();
+        loop {
+            let mut reference = ptr::null_mut();
+            match raw::git_reference_next(&mut reference, iter) {
+                raw::GIT_ITEROVER => return Ok(names),
+                code => { check(code)?; }
+            }
+
+            let name = char_ptr_to_str(&reference, raw::git_reference_name(reference))
+                .map(str::to_owned);
+            raw::git_reference_free(reference);
+            match name {
+                Some(name) => names.push(name),
+                None => return Err("reference name is not well-formed UTF-8".to_string().into()),
+            }
+        }
+    }
 }
 
 use std::marker::PhantomData;
@@ -180,6 +261,47 @@ impl Repository {
         }
         Ok(Commit {raw: commit, _marker: PhantomData})
     }
+
+    /// Return the first parent of `commit`, or `None` if `commit` is a root commit. Following
+    /// first parents from `HEAD` walks the history the way `git log --first-parent` does.
+    ///
+    /// The parent borrows from the repository, not from `commit`, so `commit` may be dropped while
+    /// the parent is still in use.
+    pub fn parent<'repo>(&'repo self, commit: &Commit<'repo>) -> Result<Option<Commit<'repo>>> {
+        unsafe {
+            if raw::git_commit_parentcount(commit.raw) == 0 {
+                return Ok(None);
+            }
+
+            let mut parent = ptr::null_mut();
+            check(raw::git_commit_parent(&mut parent, commit.raw, 0))?;
+            Ok(Some(Commit {raw: parent, _marker: PhantomData}))
+        }
+    }
+}
+
+impl Repository {
+    /// Return the contents of the blob (a file's contents, as Git stores them) identified by `oid`.
+    /// The bytes belong to the `git_blob`, which is freed before returning, so they're copied into
+    /// a `Vec` the caller owns rather than borrowed.
+    pub fn read_blob(&self, oid: &Oid) -> Result<Vec<u8>> {
+        let mut blob = ptr::null_mut();
+        unsafe {
+            check(raw::git_blob_lookup(&mut blob, self.raw, &oid.raw))?;
+
+            let content = raw::git_blob_rawcontent(blob) as *const u8;
+            let size = raw::git_blob_rawsize(blob) as usize;
+            // An empty blob's content pointer may be null, which `from_raw_parts` doesn't allow.
+            let bytes = if size == 0 {
+                Vec::new()
+            } else {
+                std::slice::from_raw_parts(content, size).to_vec()
+            };
+
+            raw::git_blob_free(blob);
+            Ok(bytes)
+        }
+    }
 }
 
 /// When a commit is dropped it must free its raw::git_commit:
@@ -194,6 +316,14 @@ impl <'repo> Drop for Commit<'repo> {
 /// From a Commit, you can borrow a Signature (a name and email address) and the text of the commit
 /// message:
 impl<'repo> Commit<'repo> {
+    /// Return the identifier of this commit. The `git_oid` libgit2 hands back lives inside the
+    /// `git_commit`, so copy it out rather than tying the `Oid` to the commit's lifetime.
+    pub fn id(&self) -> Oid {
+        unsafe {
+            Oid { raw: raw::git_oid { id: (*raw::git_commit_id(self.raw)).id } }
+        }
+    }
+
     pub fn author(&self) -> Signature {
         unsafe {
             Signature {
@@ -236,6 +366,138 @@ impl <'text> Signature<'text> {
             char_ptr_to_str(self, (*self.raw).email)
         }
     }
+
+    /// Return the time of the signature, in seconds since the Unix epoch.
+    pub fn when(&self) -> i64 {
+        unsafe {
+            (*self.raw).when.time
+        }
+    }
+
+    /// Return the signer's offset from UTC at the time of the signature, in minutes. Since `when`
+    /// is in seconds, multiply the offset by 60 before adding it to get the signer's local time.
+    pub fn offset_minutes(&self) -> i32 {
+        unsafe {
+            (*self.raw).when.offset
+        }
+    }
+}
+
+/// Format a commit the way `git log` shows it: the commit's id, its author, and the first line of
+/// its message, indented.
+pub fn format_commit(commit: &Commit) -> String {
+    let author = commit.author();
+    let oid = commit.id();
+    format_log_entry(&oid.to_string(),
+                     author.name().unwrap_or("(none)"),
+                     author.email().unwrap_or("none"),
+                     commit.message().unwrap_or("(none)"))
+}
+
+/// The layout used by `format_commit`, kept apart from libgit2 so it can be checked on its own.
+fn format_log_entry(oid: &str, name: &str, email: &str, message: &str) -> String {
+    let summary = message.lines().next().unwrap_or("");
+    format!("commit {oid}\nAuthor: {name} <{email}>\n\n    {summary}\n")
+}
+
+#[test]
+fn test_format_log_entry() {
+    let entry = format_log_entry("5ba9bd2d1e0a3c8a47e9ee4c1d7b4d9c26d2f0aa",
+                                 "Jim Blandy", "jimb@red-bean.com",
+                                 "Fix the frobnicator\n\nIt was frobbing twice.\n");
+    assert_eq!(entry,
+               "commit 5ba9bd2d1e0a3c8a47e9ee4c1d7b4d9c26d2f0aa\n\
+                Author: Jim Blandy <jimb@red-bean.com>\n\
+                \n    Fix the frobnicator\n");
+}
+
+/// The tests that need a Git repository are ignored by default. To run them, point
+/// `GIT_TOY_TEST_REPO` at a repository with at least three commits, and pass `--ignored`:
+///
+/// ```text
+/// GIT_TOY_TEST_REPO=/path/to/repo cargo test -- --ignored
+/// ```
+#[cfg(test)]
+fn test_repository() -> Repository {
+    let path = std::env::var_os("GIT_TOY_TEST_REPO")
+        .expect("set GIT_TOY_TEST_REPO to the path of a Git repository");
+    Repository::open(path).expect("opening test repository")
+}
+
+#[test]
+#[ignore = "needs a Git repository in GIT_TOY_TEST_REPO"]
+fn test_format_commit() {
+    let repo = test_repository();
+    let oid = repo.reference_name_to_id("HEAD").unwrap();
+    let commit = repo.find_commit(&oid).unwrap();
+    let author = commit.author();
+
+    let entry = format_commit(&commit);
+    let lines: Vec<&str> = entry.lines().collect();
+    assert_eq!(lines[0], format!("commit {oid}"));
+    assert_eq!(lines[1], format!("Author: {} <{}>", author.name().unwrap(), author.email().unwrap()));
+    assert_eq!(lines[2], "");
+    assert_eq!(lines[3].trim_start(), commit.message().unwrap().lines().next().unwrap());
+}
+
+#[test]
+#[ignore = "needs a Git repository in GIT_TOY_TEST_REPO"]
+fn test_signature_when() {
+    let repo = test_repository();
+    let head = repo.find_commit(&repo.reference_name_to_id("HEAD").unwrap()).unwrap();
+    let author = head.author();
+
+    // Git itself dates from 2005, so no real commit predates that.
+    assert!(author.when() > 1_100_000_000);
+    // UTC offsets run from -12:00 to +14:00.
+    assert!((-12 * 60..=14 * 60).contains(&author.offset_minutes()));
+}
+
+#[test]
+#[ignore = "needs a Git repository in GIT_TOY_TEST_REPO"]
+fn test_references() {
+    let repo = test_repository();
+    let references = repo.references().unwrap();
+    assert!(references.iter().any(|name| name.starts_with("refs/heads/")));
+}
+
+#[test]
+#[ignore = "needs a Git repository in GIT_TOY_TEST_REPO"]
+fn test_read_blob() {
+    let repo = test_repository();
+
+    // Ask `git ls-tree` for a blob in HEAD's tree, and its size. Each line reads
+    // `<mode> <type> <oid> <size>\t<path>`.
+    let path = std::env::var_os("GIT_TOY_TEST_REPO").unwrap();
+    let output = std::process::Command::new("git")
+        .arg("-C").arg(path)
+        .args(["ls-tree", "-l", "HEAD"])
+        .output()
+        .expect("running git ls-tree");
+    let listing = String::from_utf8(output.stdout).unwrap();
+    let fields: Vec<&str> = listing.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>())
+        .find(|fields| fields[1] == "blob")
+        .expect("HEAD's tree should contain a file");
+
+    let oid: Oid = fields[2].parse().unwrap();
+    let size: usize = fields[3].parse().unwrap();
+
+    assert_eq!(repo.read_blob(&oid).unwrap().len(), size);
+}
+
+#[test]
+#[ignore = "needs a Git repository in GIT_TOY_TEST_REPO"]
+fn test_parent() {
+    let repo = test_repository();
+    let head = repo.find_commit(&repo.reference_name_to_id("HEAD").unwrap()).unwrap();
+    let parent = repo.parent(&head).unwrap().expect("HEAD should have a parent");
+    let grandparent = repo.parent(&parent).unwrap().expect("HEAD~1 should have a parent");
+
+    let ids = [head.id().to_string(), parent.id().to_string(), grandparent.id().to_string()];
+    assert_ne!(ids[0], ids[1]);
+    assert_ne!(ids[1], ids[2]);
+    assert_ne!(ids[0], ids[2]);
 }
 
 /// Try to borrow a `&str` from `ptr`, given that `ptr` may be null or refer to ill-formed UTF-8.
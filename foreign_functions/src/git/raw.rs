@@ -1,7 +1,7 @@
 #![allow(non_camel_case_types)]
 
 use std::ffi::CStr;
-use std::os::raw::{c_int, c_char, c_uchar};
+use std::os::raw::{c_int, c_char, c_uchar, c_uint, c_void};
 
 #[link(name="git2")]
 extern {
@@ -21,8 +21,31 @@ extern {
                             repo: *mut git_repository,
                             id: *const git_oid) -> c_int;
 
+    pub fn git_commit_id(commit: *const git_commit) -> *const git_oid;
     pub fn git_commit_author(commit: *const git_commit) -> *const git_signature;
     pub fn git_commit_message(commit: *const git_commit) -> *const c_char;
+    pub fn git_commit_parentcount(commit: *const git_commit) -> c_uint;
+    pub fn git_commit_parent(out: *mut *mut git_commit,
+                             commit: *const git_commit,
+                             n: c_uint) -> c_int;
+
+    pub fn git_reference_iterator_new(out: *mut *mut git_reference_iterator,
+                                      repo: *mut git_repository) -> c_int;
+    pub fn git_reference_next(out: *mut *mut git_reference,
+                              iter: *mut git_reference_iterator) -> c_int;
+    pub fn git_reference_iterator_free(iter: *mut git_reference_iterator);
+    pub fn git_reference_name(reference: *const git_reference) -> *const c_char;
+    pub fn git_reference_free(reference: *mut git_reference);
+
+    pub fn git_blob_lookup(out: *mut *mut git_blob,
+                           repo: *mut git_repository,
+                           id: *const git_oid) -> c_int;
+    pub fn git_blob_rawcontent(blob: *const git_blob) -> *const c_void;
+    pub fn git_blob_rawsize(blob: *const git_blob) -> git_object_size_t;
+    pub fn git_blob_free(blob: *mut git_blob);
+
+    pub fn git_oid_tostr(out: *mut c_char, n: libc::size_t, id: *const git_oid) -> *mut c_char;
+    pub fn git_oid_fromstr(out: *mut git_oid, str: *const c_char) -> c_int;
     pub fn git_commit_free(commit: *mut git_commit);
 }
 
@@ -32,13 +55,21 @@ extern {
 /// raw pointers.
 #[repr(C)] pub struct git_repository { _private: [u8; 0]}
 #[repr(C)] pub struct git_commit { _private: [u8; 0]}
+#[repr(C)] pub struct git_reference { _private: [u8; 0]}
+#[repr(C)] pub struct git_reference_iterator { _private: [u8; 0]}
+#[repr(C)] pub struct git_blob { _private: [u8; 0]}
 #[repr(C)]
 pub struct git_error {
     pub message: *const c_char,
     pub klass: c_int
 }
 
+/// The code libgit2's iterator functions, like `git_reference_next`, return once there are no more
+/// items.
+pub const GIT_ITEROVER: c_int = -31;
+
 pub const GIT_OID_RAWSZ: usize = 20;
+pub const GIT_OID_HEXSZ: usize = GIT_OID_RAWSZ * 2;
 
 #[repr(C)]
 pub struct git_oid {
@@ -46,6 +77,7 @@ pub struct git_oid {
 }
 
 pub type git_time_t = i64;
+pub type git_object_size_t = u64;
 
 #[repr(C)]
 pub struct git_time {
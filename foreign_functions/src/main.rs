@@ -38,9 +38,5 @@ fn main() {
 
     let commit = repo.find_commit(&commit_oid).expect("looking up commit");
 
-    let author = commit.author();
-
-    println!("{} <{}>\n", author.name().unwrap_or("(none)"), author.email().unwrap_or("none"));
-
-    println!("{}", commit.message().unwrap_or("(none)"));
+    print!("{}", git::format_commit(&commit));
 }
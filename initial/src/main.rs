@@ -73,6 +73,43 @@ fn test_gcd() {
     assert_eq!(gcd(2*3*5*11*17, 3*7*11*13*19), 3*11);
 }
 
+// The extended Euclidean algorithm does the same repeated division as gcd, but also tracks how
+// to write the divisor as a combination of the inputs: it returns (g, x, y) with a*x + b*y == g.
+// Those coefficients can be negative, so this works on signed integers.
+fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+// The inverse of `a` modulo `m` is the x in 0..m with a*x % m == 1. It exists only when `a` and
+// `m` share no factors, in which case extended_gcd gives a*x + m*y == 1, and x is the answer.
+fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    if m <= 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    if g != 1 {
+        return None;
+    }
+    Some(x.rem_euclid(m))
+}
+
+#[test]
+fn test_mod_inverse() {
+    assert_eq!(mod_inverse(3, 11), Some(4));
+    assert_eq!(mod_inverse(2, 4), None);
+
+    for &(a, m) in &[(3, 11), (10, 17), (7, 40), (123, 4567), (-3, 11)] {
+        let inv = mod_inverse(a, m).unwrap();
+        assert!((0..m).contains(&inv));
+        assert_eq!((a * inv).rem_euclid(m), 1);
+    }
+}
+
 /* A Rust package, whether a library or an executable, is called a `crate`; Cargo and crates.io both
 derive their names from this term.
  */
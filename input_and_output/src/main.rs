@@ -53,3 +53,38 @@ fn copy_example<R: ?Sized, W: ?Sized>(reader: &mut R, writer: &mut W) -> io::Res
         written += len as u64;
     }
 }
+
+/// Like `copy_example`, but reading into a buffer of `buf_size` bytes rather than a fixed 8 KiB, so
+/// callers can trade memory for fewer calls to `read` and `write`.
+///
+/// Panics if `buf_size` is zero: reading into an empty buffer always returns 0, which would look
+/// like the end of the input.
+pub fn copy_with_buffer<R: Read + ?Sized, W: Write + ?Sized>(reader: &mut R, writer: &mut W,
+                                                             buf_size: usize) -> io::Result<u64>
+{
+    assert!(buf_size > 0, "copy_with_buffer needs a non-empty buffer");
+    let mut buf = vec![0; buf_size];
+    let mut written = 0;
+    loop {
+        let len = match reader.read(&mut buf) {
+            Ok(0) => return Ok(written),
+            Ok(len) => len,
+            Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        writer.write_all(&buf[..len])?;
+        written += len as u64;
+    }
+}
+
+#[test]
+fn test_copy_with_buffer() {
+    let data = b"The quick brown fox jumps over the lazy dog.";
+
+    for buf_size in [1, 3, data.len() * 2] {
+        let mut reader: &[u8] = data;
+        let mut out = Vec::new();
+        assert_eq!(copy_with_buffer(&mut reader, &mut out, buf_size).unwrap(), data.len() as u64);
+        assert_eq!(out, data);
+    }
+}
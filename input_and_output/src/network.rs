@@ -1,5 +1,5 @@
-use std::net::TcpListener;
-use std::io;
+use std::net::{TcpListener, TcpStream};
+use std::io::{self, Read, Write};
 use std::thread::spawn;
 use std::error::Error;
 use reqwest;
@@ -11,17 +11,60 @@ pub(crate) fn echo_main(addr: &str) -> io::Result<()> {
     println!("listening on {addr}");
 
     loop {
-        let (mut stream, addr) = listener.accept()?;
+        let (stream, addr) = listener.accept()?;
         println!("Connection received from {addr}");
 
-        let mut write_stream = stream.try_clone()?;
         spawn(move || {
-            io::copy(&mut stream, &mut write_stream).expect("error in client thread: ");
+            run_echo(stream, &mut io::sink()).expect("error in client thread: ");
             println!("connection closed");
         });
     }
 }
 
+/// Echo everything received on `stream` back to it until the client closes its side, also writing
+/// a copy of the echoed bytes to `log`. Like `say_hello` in traits_and_generics, taking any
+/// `W: Write` means the log can be a file, stdout, or an in-memory `Vec<u8>` in a test.
+pub(crate) fn run_echo<W: Write>(mut stream: TcpStream, log: &mut W) -> io::Result<()> {
+    let mut write_stream = stream.try_clone()?;
+    let mut buf = [0; 1024];
+    loop {
+        let len = match stream.read(&mut buf) {
+            Ok(0) => return Ok(()),
+            Ok(len) => len,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        write_stream.write_all(&buf[..len])?;
+        log.write_all(&buf[..len])?;
+    }
+}
+
+#[test]
+fn test_run_echo() {
+    use std::net::Shutdown;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let client = spawn(move || {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(b"hello, ").unwrap();
+        stream.write_all(b"echo").unwrap();
+        stream.shutdown(Shutdown::Write).unwrap();
+        let mut echoed = String::new();
+        stream.read_to_string(&mut echoed).unwrap();
+        echoed
+    });
+
+    let (stream, _) = listener.accept().unwrap();
+    let mut log = Vec::new();
+    run_echo(stream, &mut log).unwrap();
+
+    assert_eq!(log, b"hello, echo");
+    assert_eq!(client.join().unwrap(), "hello, echo");
+}
+
+
 pub(crate) fn http_get_main(url: &str) -> Result<(), Box<dyn Error>> {
     let mut response = reqwest::blocking::get(url)?;
     if !response.status().is_success() {
@@ -2,7 +2,7 @@ use std::error::Error;
 use std::io::{self, BufReader};
 use std::io::prelude::*;
 use std::fs::File;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Buffered Readers
 /// For efficiency, readers and writers can be buffered, which simply means they have a chunk of
@@ -60,3 +60,128 @@ pub(crate) fn grep_main() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+
+/// Like `grep`, but ignore ASCII case when matching, and return the matching lines instead of
+/// printing them. Comparing bytes with `eq_ignore_ascii_case` lowercases both sides as it goes,
+/// without making a lowercased copy of each line. An empty `target` matches every line.
+pub(crate) fn grep_ci(target: &str, reader: impl BufRead) -> io::Result<Vec<String>> {
+    let target = target.as_bytes();
+    let mut matches = Vec::new();
+    for line_result in reader.lines() {
+        let line = line_result?;
+        // `windows(0)` panics, so the empty target needs handling of its own.
+        if target.is_empty()
+            || line.as_bytes().windows(target.len())
+                .any(|window| window.eq_ignore_ascii_case(target))
+        {
+            matches.push(line);
+        }
+    }
+    Ok(matches)
+}
+
+#[test]
+fn test_grep_ci() {
+    let text = "hello world\ngoodbye world\nOh, HeLLo again\n";
+    assert_eq!(grep_ci("HELLO", text.as_bytes()).unwrap(),
+               vec!["hello world", "Oh, HeLLo again"]);
+    assert_eq!(grep_ci("farewell", text.as_bytes()).unwrap(), Vec::<String>::new());
+    assert_eq!(grep_ci("", text.as_bytes()).unwrap().len(), 3);
+}
+
+/// A line matched by `grep_in_files`: the file it came from, its line number (counting from 1), and
+/// the text of the line.
+pub(crate) type GrepMatch = (PathBuf, usize, String);
+
+/// A file `grep_in_files` couldn't open, and why.
+pub(crate) type GrepFailure = (PathBuf, io::Error);
+
+/// Search each of `files` for lines containing `target`. Unlike `grep_main`, which gives up at the
+/// first file it can't open, this moves on to the next file, returning the files that couldn't be
+/// opened alongside the matches. An error reading a file that did open is still returned as an
+/// error.
+pub(crate) fn grep_in_files<P: AsRef<Path>>(target: &str, files: &[P])
+    -> io::Result<(Vec<GrepMatch>, Vec<GrepFailure>)>
+{
+    let mut matches = Vec::new();
+    let mut errors = Vec::new();
+
+    for file in files {
+        let path = file.as_ref().to_path_buf();
+        let f = match File::open(&path) {
+            Ok(f) => f,
+            Err(err) => {
+                errors.push((path, err));
+                continue;
+            }
+        };
+        for (i, line_result) in BufReader::new(f).lines().enumerate() {
+            let line = line_result?;
+            if line.contains(target) {
+                matches.push((path.clone(), i + 1, line));
+            }
+        }
+    }
+
+    Ok((matches, errors))
+}
+
+#[test]
+fn test_grep_in_files() {
+    let dir = std::env::temp_dir().join(format!("grep_in_files_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let first = dir.join("first.txt");
+    let second = dir.join("second.txt");
+    let missing = dir.join("missing.txt");
+    std::fs::write(&first, "apple\nbanana\npineapple\n").unwrap();
+    std::fs::write(&second, "cherry\napple pie\n").unwrap();
+
+    let (matches, errors) = grep_in_files("apple", &[&first, &missing, &second]).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(matches, vec![
+        (first.clone(), 1, "apple".to_string()),
+        (first, 3, "pineapple".to_string()),
+        (second, 2, "apple pie".to_string()),
+    ]);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, missing);
+    assert_eq!(errors[0].1.kind(), io::ErrorKind::NotFound);
+}
+
+/// Split one line of CSV into its fields. Commas separate fields, except inside a double-quoted
+/// span, where they are ordinary characters. Within quotes, a doubled quote `""` stands for a
+/// single `"`.
+pub(crate) fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' => in_quotes = true,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+}
+
+#[test]
+fn test_split_csv_line() {
+    assert_eq!(split_csv_line("one,two,,three"), vec!["one", "two", "", "three"]);
+    assert_eq!(split_csv_line(r#"x,"a,b",y"#), vec!["x", "a,b", "y"]);
+    assert_eq!(split_csv_line(r#""say ""hi""",done"#), vec![r#"say "hi""#, "done"]);
+}
@@ -1,5 +1,5 @@
 use serde_json;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::ffi::OsStr;
 
 /// Like readers, writers are closed automatically when they are dropped. Just as BufReader::new(reader)
@@ -44,6 +44,47 @@ pub(crate) fn write_json() {
     serde_json::to_writer(&mut std::io::stdout(), &map).unwrap();
 }
 
+/// Write each of `items` as one line of JSON: `serde_json::to_writer` never emits a newline within
+/// a value, so a reader can split the output into lines and parse each one on its own.
+pub(crate) fn write_json_lines<W, T>(writer: &mut W, items: impl IntoIterator<Item = T>)
+    -> serde_json::Result<()>
+    where W: std::io::Write, T: serde::Serialize
+{
+    for item in items {
+        serde_json::to_writer(&mut *writer, &item)?;
+        writer.write_all(b"\n").map_err(serde_json::Error::io)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_write_json_lines() {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Event {
+        id: u32,
+        message: String,
+    }
+
+    let events = vec![
+        Event { id: 1, message: "started".to_string() },
+        Event { id: 2, message: "two\nlines".to_string() },
+        Event { id: 3, message: "stopped".to_string() },
+    ];
+
+    let mut out = Vec::new();
+    write_json_lines(&mut out, &events).unwrap();
+
+    let text = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 3);
+    let parsed: Vec<Event> = lines.iter()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(parsed, events);
+}
+
 /// OsStr is a string type that's a superset of UTF-8. Its job is to be able to represent all filenames,
 /// command-line arguments, and environment variables on the current system, whether they're valid
 /// Unicode or not. On Unix, as OsStr can hold any sequence of bytes. On Windows, an OsStr is stored
@@ -79,6 +120,26 @@ pub(crate) fn paths() {
 }
 
 
+/// Give `path` the extension `ext`, replacing its current extension if it has one, and appending
+/// `ext` if not. An `ext` containing a `.` or a path separator would change more than the extension,
+/// so in that case `path` is returned unchanged.
+pub(crate) fn with_extension_checked(path: &Path, ext: &str) -> PathBuf {
+    if ext.contains('.') || ext.chars().any(std::path::is_separator) {
+        return path.to_path_buf();
+    }
+    path.with_extension(ext)
+}
+
+#[test]
+fn test_with_extension_checked() {
+    assert_eq!(with_extension_checked(Path::new("a/b.txt"), "pdf"), Path::new("a/b.pdf"));
+    assert_eq!(with_extension_checked(Path::new("a/b"), "pdf"), Path::new("a/b.pdf"));
+    assert_eq!(with_extension_checked(Path::new("a/b.tar.gz"), "bz2"), Path::new("a/b.tar.bz2"));
+    assert_eq!(with_extension_checked(Path::new("a/b.txt"), "tar.gz"), Path::new("a/b.txt"));
+    assert_eq!(with_extension_checked(Path::new("a/b.txt"), "../pdf"), Path::new("a/b.txt"));
+    assert_eq!(with_extension_checked(Path::new("a/b.txt"), "x/y"), Path::new("a/b.txt"));
+}
+
 use std::{fs, io};
 
 /// Copy the existing directory `src` to the target path `dst`.
@@ -110,6 +171,123 @@ pub(crate) fn copy_to(src: &Path, src_type: &fs::FileType, dst: &Path) -> io::Re
     Ok(())
 }
 
+/// Options for `copy_dir_to_with`.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct CopyOptions {
+    /// Copy what symbolic links point to, rather than recreating the links themselves.
+    pub(crate) follow_symlinks: bool,
+    /// Replace files that already exist at the destination, rather than skipping them.
+    pub(crate) overwrite: bool,
+}
+
+/// Like `copy_dir_to`, but as directed by `options`, and also copying symbolic links. Return the
+/// destination paths that were skipped because they already existed and `options.overwrite` was
+/// false.
+pub(crate) fn copy_dir_to_with(src: &Path, dst: &Path, options: &CopyOptions)
+    -> io::Result<Vec<PathBuf>>
+{
+    let mut skipped = Vec::new();
+    copy_dir_contents(src, dst, options, &mut skipped)?;
+    Ok(skipped)
+}
+
+fn copy_dir_contents(src: &Path, dst: &Path, options: &CopyOptions, skipped: &mut Vec<PathBuf>)
+    -> io::Result<()>
+{
+    if !dst.is_dir() {
+        fs::create_dir(dst)?;
+    }
+
+    for entry_result in src.read_dir()? {
+        let entry = entry_result?;
+        let src_path = entry.path();
+        let dst_path = dst.join(entry.file_name());
+
+        // `DirEntry::file_type` describes a symbolic link itself; `fs::metadata` follows it.
+        let mut file_type = entry.file_type()?;
+        if file_type.is_symlink() && options.follow_symlinks {
+            file_type = fs::metadata(&src_path)?.file_type();
+        }
+
+        if file_type.is_dir() {
+            copy_dir_contents(&src_path, &dst_path, options, skipped)?;
+            continue;
+        }
+
+        if fs::symlink_metadata(&dst_path).is_ok() {
+            if !options.overwrite {
+                skipped.push(dst_path);
+                continue;
+            }
+            fs::remove_file(&dst_path)?;
+        }
+
+        if file_type.is_symlink() {
+            symlink(src_path.read_link()?, &dst_path)?;
+        } else {
+            copy_to(&src_path, &file_type, &dst_path)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("{name}_{}", std::process::id()));
+    if dir.exists() {
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_copy_dir_to_with_skips_existing() {
+    let root = scratch_dir("copy_dir_to_with_skip");
+    let (src, dst) = (root.join("src"), root.join("dst"));
+    fs::create_dir_all(src.join("sub")).unwrap();
+    fs::write(src.join("a.txt"), "new a").unwrap();
+    fs::write(src.join("sub/b.txt"), "new b").unwrap();
+    fs::create_dir_all(&dst).unwrap();
+    fs::write(dst.join("a.txt"), "old a").unwrap();
+
+    let skipped = copy_dir_to_with(&src, &dst, &CopyOptions::default()).unwrap();
+    assert_eq!(skipped, vec![dst.join("a.txt")]);
+    assert_eq!(fs::read_to_string(dst.join("a.txt")).unwrap(), "old a");
+    assert_eq!(fs::read_to_string(dst.join("sub/b.txt")).unwrap(), "new b");
+
+    let options = CopyOptions { overwrite: true, ..CopyOptions::default() };
+    let skipped = copy_dir_to_with(&src, &dst, &options).unwrap();
+    assert!(skipped.is_empty());
+    assert_eq!(fs::read_to_string(dst.join("a.txt")).unwrap(), "new a");
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_copy_dir_to_with_symlinks() {
+    let root = scratch_dir("copy_dir_to_with_symlinks");
+    let src = root.join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("target.txt"), "pointed at").unwrap();
+    symlink("target.txt", src.join("link.txt")).unwrap();
+
+    let kept = root.join("kept");
+    copy_dir_to_with(&src, &kept, &CopyOptions::default()).unwrap();
+    assert!(fs::symlink_metadata(kept.join("link.txt")).unwrap().file_type().is_symlink());
+    assert_eq!(fs::read_link(kept.join("link.txt")).unwrap(), Path::new("target.txt"));
+
+    let followed = root.join("followed");
+    let options = CopyOptions { follow_symlinks: true, ..CopyOptions::default() };
+    copy_dir_to_with(&src, &followed, &options).unwrap();
+    assert!(fs::symlink_metadata(followed.join("link.txt")).unwrap().file_type().is_file());
+    assert_eq!(fs::read_to_string(followed.join("link.txt")).unwrap(), "pointed at");
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
 
 #[cfg(unix)]
 use std::os::unix::fs::symlink;
@@ -121,3 +299,58 @@ fn symlink<P: AsRef<Path>, Q: AsRef<Path>>(src: P, _dst:Q) -> std::io::Result<()
     Err(io::Error::new(io::ErrorKind::Other,
                         format!("can't copy symbolic link: {}", src.as_ref().display())))
 }
+
+
+use std::io::Write;
+
+/// Write `rows` as a table of left-aligned columns separated by two spaces, one row per line. Each
+/// column is as wide as its widest cell. Rows may have differing numbers of cells; the missing
+/// ones are treated as empty. The last cell of each row is not padded, so lines carry no trailing
+/// whitespace.
+pub(crate) fn write_table<W: Write>(out: &mut W, rows: &[Vec<String>]) -> io::Result<()> {
+    let mut widths: Vec<usize> = Vec::new();
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            let width = cell.chars().count();
+            if i == widths.len() {
+                widths.push(width);
+            } else if width > widths[i] {
+                widths[i] = width;
+            }
+        }
+    }
+
+    for row in rows {
+        let mut line = String::new();
+        for (i, width) in widths.iter().enumerate() {
+            let cell = row.get(i).map(String::as_str).unwrap_or("");
+            if i + 1 == widths.len() {
+                line.push_str(cell);
+            } else {
+                line.push_str(&format!("{:<width$}  ", cell, width = width));
+            }
+        }
+        writeln!(out, "{}", line.trim_end())?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_write_table() {
+    let rows: Vec<Vec<String>> = vec![
+        vec!["name", "kind", "size"],
+        vec!["Cargo.toml", "file", "512"],
+        vec!["src", "dir"],
+    ].into_iter()
+        .map(|row| row.into_iter().map(String::from).collect())
+        .collect();
+
+    let mut out = Vec::new();
+    write_table(&mut out, &rows).unwrap();
+
+    assert_eq!(String::from_utf8(out).unwrap(),
+               "name        kind  size\n\
+                Cargo.toml  file  512\n\
+                src         dir\n");
+}
@@ -17,6 +17,48 @@ impl Iterator for I32Range {
         result
     }
 }
+
+/// An iterator over the items of another iterator, gathered into vectors of `size` items. Unlike
+/// a slice's `chunks` method, this works on any iterator, at the cost of copying items into a new
+/// `Vec` for each chunk.
+pub(crate) struct Chunked<I> {
+    iter: I,
+    size: usize,
+}
+
+impl<I: Iterator> Iterator for Chunked<I> {
+    type Item = Vec<I::Item>;
+    fn next(&mut self) -> Option<Vec<I::Item>> {
+        let chunk: Vec<I::Item> = self.iter.by_ref().take(self.size).collect();
+        if chunk.is_empty() {
+            return None;
+        }
+        Some(chunk)
+    }
+}
+
+/// Split `iter` into chunks of `size` items each. The last chunk is shorter if `iter`'s length
+/// isn't a multiple of `size`. Panics if `size` is zero, since no number of empty chunks would
+/// ever get through the items.
+pub(crate) fn chunked<I: Iterator>(iter: I, size: usize) -> impl Iterator<Item=Vec<I::Item>> {
+    assert!(size != 0, "chunk size must be non-zero");
+    Chunked { iter, size }
+}
+
+#[test]
+fn test_chunked() {
+    assert_eq!(chunked(1..=6, 2).collect::<Vec<_>>(), vec![vec![1, 2], vec![3, 4], vec![5, 6]]);
+    assert_eq!(chunked(1..=7, 3).collect::<Vec<_>>(), vec![vec![1, 2, 3], vec![4, 5, 6], vec![7]]);
+    assert_eq!(chunked("ab".chars(), 5).collect::<Vec<_>>(), vec![vec!['a', 'b']]);
+    assert_eq!(chunked(std::iter::empty::<i32>(), 3).count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "chunk size must be non-zero")]
+fn test_chunked_zero_size() {
+    let _ = chunked(1..10, 0);
+}
+
 use enums_and_patterns::enums::{BinaryTree::*, BinaryTree, TreeNode};
 
 /// The classic way to walk a binary tree is to recurse, using the stack of function calls to keep
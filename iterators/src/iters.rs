@@ -48,6 +48,29 @@ pub(crate) fn fibonacci() -> impl Iterator<Item=usize> {
     })
 }
 
+/// Like `fibonacci`, but stop before the first number that isn't less than `limit`. Each item
+/// here is a pair of consecutive Fibonacci numbers, so `successors` has what it needs to produce
+/// the next pair. Near `usize::MAX` the addition saturates instead of overflowing; since
+/// `usize::MAX` is never less than `limit`, that ends the sequence just the same.
+pub(crate) fn fibonacci_up_to(limit: usize) -> impl Iterator<Item=usize> {
+    successors(Some((1usize, 1usize)), |&(a, b)| Some((b, a.saturating_add(b))))
+        .map(|(a, _b)| a)
+        .take_while(move |&n| n < limit)
+}
+
+#[test]
+fn test_fibonacci_up_to() {
+    assert_eq!(fibonacci_up_to(30).collect::<Vec<_>>(), vec![1, 1, 2, 3, 5, 8, 13, 21]);
+    assert_eq!(fibonacci_up_to(21).collect::<Vec<_>>(), vec![1, 1, 2, 3, 5, 8, 13]);
+    assert_eq!(fibonacci_up_to(1).count(), 0);
+
+    // The largest Fibonacci numbers that fit in a usize still come out, and nothing past them.
+    let all: Vec<usize> = fibonacci_up_to(usize::MAX).collect();
+    let n = all.len();
+    assert_eq!(all[n - 1], all[n - 2] + all[n - 3]);
+    assert_eq!(all[n - 1].checked_add(all[n - 2]), None);
+}
+
 
 /// Iterator Adapters
 /// Once you have an iterator in hand, the Iterator trait provides a broad selection of adapter
@@ -72,6 +72,8 @@ fn main() {
 
     assert_eq!(iters::fibonacci().take(8).collect::<Vec<_>>(),
                     vec![1,1,2,3,5,8,13,21]);
+    assert_eq!(iters::fibonacci_up_to(30).collect::<Vec<_>>(),
+                    vec![1,1,2,3,5,8,13,21]);
 
     /// Many collection types provide a drain method that takes a mutable reference to the collection
     /// and returns an iterator that passes ownership of each element to the consumer. However, unlike
@@ -128,6 +130,11 @@ fn main() {
 
     moreiters::cycle();
 
+    assert_eq!(moreiters::moving_average(&[1.0, 2.0, 3.0, 4.0], 2), vec![1.5, 2.5, 3.5]);
+
+    assert_eq!(moreiters::group_runs("aaabcc".chars(), |&c| c),
+                vec![('a', vec!['a', 'a', 'a']), ('b', vec!['b']), ('c', vec!['c', 'c'])]);
+
     consumingiters::count_sum_product();
 
     consumingiters::min_max();
@@ -162,6 +169,9 @@ fn main() {
 
     assert_eq!(pi as f32, std::f32::consts::PI);
 
+    assert_eq!(customiters::chunked(0..5, 2).collect::<Vec<_>>(),
+                vec![vec![0, 1], vec![2, 3], vec![4]]);
+
     let mut tree = BinaryTree::Empty;
     tree.add("jaeger");
     tree.add("robot");
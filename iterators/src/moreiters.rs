@@ -180,3 +180,60 @@ pub(crate) fn cycle() -> () {
         println!("{line}");
     }
 }
+
+
+/// windows
+/// Slices have iterator-producing methods of their own. windows(n) yields every contiguous
+/// subslice of length n, each overlapping the last in all but one element, which is exactly what a
+/// moving average needs. Asking for windows of length zero panics, so that case is handled first;
+/// a window longer than the slice simply yields nothing.
+pub(crate) fn moving_average(data: &[f64], window: usize) -> Vec<f64> {
+    if window == 0 {
+        return Vec::new();
+    }
+
+    data.windows(window)
+        .map(|w| w.iter().sum::<f64>() / window as f64)
+        .collect()
+}
+
+#[test]
+fn test_moving_average() {
+    assert_eq!(moving_average(&[1.0, 2.0, 3.0, 4.0], 2), vec![1.5, 2.5, 3.5]);
+    assert_eq!(moving_average(&[1.0, 2.0, 3.0, 4.0], 4), vec![2.5]);
+    assert_eq!(moving_average(&[], 2), Vec::<f64>::new());
+    assert_eq!(moving_average(&[1.0, 2.0, 3.0], 4), Vec::<f64>::new());
+    assert_eq!(moving_average(&[1.0, 2.0, 3.0], 0), Vec::<f64>::new());
+}
+
+
+/// Group consecutive items whose keys are equal, keeping each group's key alongside its items. This
+/// is like Unix's `uniq -c`, except that it keeps the repeated values rather than just counting
+/// them. Only neighbors are grouped: a key that turns up again later starts a new group.
+pub(crate) fn group_runs<T, K, F>(items: impl IntoIterator<Item=T>, mut key: F) -> Vec<(K, Vec<T>)>
+    where K: PartialEq, F: FnMut(&T) -> K
+{
+    let mut groups: Vec<(K, Vec<T>)> = Vec::new();
+    for item in items {
+        let k = key(&item);
+        match groups.last_mut() {
+            Some((last_key, run)) if *last_key == k => run.push(item),
+            _ => groups.push((k, vec![item])),
+        }
+    }
+    groups
+}
+
+#[test]
+fn test_group_runs() {
+    assert_eq!(group_runs([1, 1, 2, 3, 3, 3, 1], |&n| n),
+               vec![(1, vec![1, 1]), (2, vec![2]), (3, vec![3, 3, 3]), (1, vec![1])]);
+
+    assert_eq!(group_runs(["apple", "avocado", "banana", "cherry", "cranberry"],
+                          |s| s.chars().next()),
+               vec![(Some('a'), vec!["apple", "avocado"]),
+                    (Some('b'), vec!["banana"]),
+                    (Some('c'), vec!["cherry", "cranberry"])]);
+
+    assert_eq!(group_runs(Vec::<i32>::new(), |&n| n), vec![]);
+}
@@ -53,6 +53,148 @@ pub enum Json {
     Object(Box<HashMap<String, Json>>)
 }
 
+/// Accessors for picking values back out of a `Json`. Each returns `None` when `self` isn't the
+/// variant it expects, so lookups can be chained with `and_then`.
+impl Json {
+    /// Look up `key`, if `self` is an object.
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.get(key),
+            _ => None,
+        }
+    }
+
+    /// Return the element at `index`, if `self` is an array.
+    pub fn at(&self, index: usize) -> Option<&Json> {
+        match self {
+            Json::Array(elements) => elements.get(index),
+            _ => None,
+        }
+    }
+
+    /// Return the number, if `self` is a number.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Borrow the text, if `self` is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+/// Formatting a `Json` with `{}` produces compact JSON text. Object members are written in sorted
+/// key order, so that the output doesn't depend on the `HashMap`'s iteration order. JSON has no way
+/// to write NaN or the infinities, so those come out as `null`.
+impl std::fmt::Display for Json {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Boolean(b) => write!(f, "{b}"),
+            Json::Number(n) if n.is_finite() => write!(f, "{n}"),
+            Json::Number(_) => write!(f, "null"),
+            Json::String(s) => write_json_string(f, s),
+            Json::Array(elements) => {
+                write!(f, "[")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{element}")?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in sorted_fields(fields).into_iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_json_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+impl Json {
+    /// Like `to_string`, but write each array element and object member on its own line, indented
+    /// `indent` spaces per level of nesting, much as `{:#?}` does for `Debug` output. Empty arrays
+    /// and objects stay on one line, as `[]` and `{}`.
+    pub fn to_pretty_string(&self, indent: usize) -> String {
+        let mut out = String::new();
+        self.write_pretty(&mut out, indent, 0);
+        out
+    }
+
+    fn write_pretty(&self, out: &mut String, indent: usize, depth: usize) {
+        let inner = " ".repeat(indent * (depth + 1));
+        let outer = " ".repeat(indent * depth);
+        match self {
+            Json::Array(elements) if !elements.is_empty() => {
+                out.push_str("[\n");
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(",\n");
+                    }
+                    out.push_str(&inner);
+                    element.write_pretty(out, indent, depth + 1);
+                }
+                out.push('\n');
+                out.push_str(&outer);
+                out.push(']');
+            }
+            Json::Object(fields) if !fields.is_empty() => {
+                out.push_str("{\n");
+                for (i, (key, value)) in sorted_fields(fields).into_iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(",\n");
+                    }
+                    out.push_str(&inner);
+                    out.push_str(&Json::String(key.clone()).to_string());
+                    out.push_str(": ");
+                    value.write_pretty(out, indent, depth + 1);
+                }
+                out.push('\n');
+                out.push_str(&outer);
+                out.push('}');
+            }
+            scalar => out.push_str(&scalar.to_string()),
+        }
+    }
+}
+
+fn sorted_fields(fields: &HashMap<String, Json>) -> Vec<(&String, &Json)> {
+    let mut sorted: Vec<_> = fields.iter().collect();
+    sorted.sort_by(|a, b| a.0.cmp(b.0));
+    sorted
+}
+
+/// Write `s` as a quoted JSON string, escaping quotes, backslashes, and control characters.
+fn write_json_string(f: &mut std::fmt::Formatter, s: &str) -> std::fmt::Result {
+    write!(f, "\"")?;
+    for ch in s.chars() {
+        match ch {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    write!(f, "\"")
+}
+
 impl From<bool> for Json {
     fn from(b: bool) -> Json {
         Json::Boolean(b)
@@ -111,6 +253,13 @@ macro_rules! json {
             $( ($crate::json::ToString::to_string($key), json!($value)) ),*
         ].into_iter().collect()))
     };
+    // The same trailing-comma trick as in `my_vec!`: match the extra comma, and recurse without it.
+    ([$($element:tt),+ ,]) => {
+        json!([$($element),*])
+    };
+    ({ $($key:tt : $value:tt),+ ,}) => {
+        json!({ $($key : $value),* })
+    };
     ($other:tt) => {
         $crate::Json::from($other)  //Handle Boolean/Number/String
     }
@@ -143,3 +292,379 @@ fn json_array_with_json_element() {
 
     assert_eq!(macro_generate_value, hand_coded_value);
 }
+
+#[test]
+fn json_with_trailing_commas() {
+    assert_eq!(json!([1, 2, 3,]),
+               Json::Array(vec![Json::Number(1.0), Json::Number(2.0), Json::Number(3.0)]));
+
+    let macro_generate_value = json!(
+        [
+            {
+                "pitch": 440.0,
+                "name": "A",
+            },
+        ]
+    );
+
+    let hand_coded_value = Json::Array(vec![
+        Json::Object(Box::new(vec![
+            ("pitch".to_string(), Json::Number(440.0)),
+            ("name".to_string(), Json::String("A".to_string())),
+        ].into_iter().collect()))
+    ]);
+
+    assert_eq!(macro_generate_value, hand_coded_value);
+}
+
+#[test]
+fn json_display() {
+    assert_eq!(json!(null).to_string(), "null");
+    assert_eq!(json!(true).to_string(), "true");
+    assert_eq!(json!(1926).to_string(), "1926");
+    assert_eq!(json!(440.5).to_string(), "440.5");
+    assert_eq!(Json::Number(f64::NAN).to_string(), "null");
+    assert_eq!(json!("say \"hi\"\\\n\u{1}").to_string(), r#""say \"hi\"\\\n\u0001""#);
+    assert_eq!(json!([]).to_string(), "[]");
+    assert_eq!(json!({}).to_string(), "{}");
+
+    let value = json!({
+        "pitch": 440.0,
+        "name": "A",
+        "harmonics": [880, 1320, null],
+        "tuned": false
+    });
+    assert_eq!(value.to_string(),
+               r#"{"harmonics":[880,1320,null],"name":"A","pitch":440,"tuned":false}"#);
+}
+
+/// `parse` reports errors with the `JsonError` type from the error_handling crate, which carries
+/// the line and column (both counting from 1) where the problem was detected.
+pub use error_handling::JsonError;
+
+/// Parse `input` as a single JSON value, surrounded by nothing but whitespace.
+///
+/// This is a recursive-descent parser: there's one method per kind of value, and the methods for
+/// arrays and objects call back into `parse_value` for their elements. Each level of nesting takes
+/// a few stack frames, so input nested more than `MAX_DEPTH` deep is rejected rather than allowed
+/// to overflow the stack.
+pub fn parse(input: &str) -> Result<Json, JsonError> {
+    let mut parser = Parser { chars: input.chars().peekable(), line: 1, column: 1, depth: 0 };
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+    match parser.peek() {
+        None => Ok(value),
+        Some(c) => Err(parser.error(format!("unexpected {c:?} after JSON value"))),
+    }
+}
+
+/// The deepest nesting of arrays and objects `parse` accepts.
+pub const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+    column: usize,
+    /// How many arrays and objects enclose the value being parsed.
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: impl Into<String>) -> JsonError {
+        JsonError::new(message, self.line, self.column)
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn expect(&mut self, expected: char, context: &str) -> Result<(), JsonError> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.next();
+                Ok(())
+            }
+            _ => Err(self.error(format!("expected '{expected}' {context}"))),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(' ' | '\t' | '\n' | '\r') = self.peek() {
+            self.next();
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Json, JsonError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(c @ ('{' | '[')) => {
+                if self.depth == MAX_DEPTH {
+                    return Err(self.error(format!("nested more than {MAX_DEPTH} levels deep")));
+                }
+                self.depth += 1;
+                let value = if c == '{' { self.parse_object() } else { self.parse_array() };
+                self.depth -= 1;
+                value
+            }
+            Some('"') => Ok(Json::String(self.parse_string()?)),
+            Some('-' | '0'..='9') => self.parse_number(),
+            Some('n') => self.parse_literal("null", Json::Null),
+            Some('t') => self.parse_literal("true", Json::Boolean(true)),
+            Some('f') => self.parse_literal("false", Json::Boolean(false)),
+            Some(c) => Err(self.error(format!("unexpected {c:?}"))),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn parse_literal(&mut self, word: &str, value: Json) -> Result<Json, JsonError> {
+        for expected in word.chars() {
+            if self.peek() != Some(expected) {
+                return Err(self.error(format!("expected '{word}'")));
+            }
+            self.next();
+        }
+        Ok(value)
+    }
+
+    /// JSON numbers are stricter than what `f64::from_str` accepts: an optional minus sign, an
+    /// integer part with no leading zeros, then optionally a fraction and an exponent, each of
+    /// which needs at least one digit. Check that grammar as we go, and leave the conversion
+    /// itself to `f64::from_str`.
+    fn parse_number(&mut self) -> Result<Json, JsonError> {
+        let (line, column) = (self.line, self.column);
+        let mut text = String::new();
+        let mut valid = true;
+
+        if self.peek() == Some('-') {
+            self.push_next(&mut text);
+        }
+        match self.peek() {
+            Some('0') => {
+                self.push_next(&mut text);
+                // Take any digits after the zero too, so the error shows the whole number.
+                valid = self.push_digits(&mut text) == 0;
+            }
+            Some('1'..='9') => {
+                self.push_digits(&mut text);
+            }
+            _ => valid = false,
+        }
+        if valid && self.peek() == Some('.') {
+            self.push_next(&mut text);
+            valid = self.push_digits(&mut text) > 0;
+        }
+        if valid && matches!(self.peek(), Some('e' | 'E')) {
+            self.push_next(&mut text);
+            if matches!(self.peek(), Some('+' | '-')) {
+                self.push_next(&mut text);
+            }
+            valid = self.push_digits(&mut text) > 0;
+        }
+
+        match text.parse::<f64>() {
+            Ok(n) if valid => Ok(Json::Number(n)),
+            _ => Err(JsonError::new(format!("invalid number {text:?}"), line, column)),
+        }
+    }
+
+    /// Move the next character onto the end of `text`.
+    fn push_next(&mut self, text: &mut String) {
+        if let Some(c) = self.next() {
+            text.push(c);
+        }
+    }
+
+    /// Move any decimal digits that come next onto the end of `text`, and return how many there
+    /// were.
+    fn push_digits(&mut self, text: &mut String) -> usize {
+        let mut count = 0;
+        while let Some('0'..='9') = self.peek() {
+            self.push_next(text);
+            count += 1;
+        }
+        count
+    }
+
+    fn parse_string(&mut self) -> Result<String, JsonError> {
+        self.expect('"', "at start of string")?;
+        let mut s = String::new();
+        loop {
+            match self.next() {
+                None => return Err(self.error("unterminated string")),
+                Some('"') => return Ok(s),
+                Some('\\') => s.push(self.parse_escape()?),
+                Some(c) if c.is_control() => {
+                    return Err(self.error("control character in string"));
+                }
+                Some(c) => s.push(c),
+            }
+        }
+    }
+
+    /// Parse what follows a backslash in a string, returning the character it stands for.
+    fn parse_escape(&mut self) -> Result<char, JsonError> {
+        match self.next() {
+            Some('"') => Ok('"'),
+            Some('\\') => Ok('\\'),
+            Some('/') => Ok('/'),
+            Some('b') => Ok('\u{8}'),
+            Some('f') => Ok('\u{c}'),
+            Some('n') => Ok('\n'),
+            Some('r') => Ok('\r'),
+            Some('t') => Ok('\t'),
+            Some('u') => {
+                let unit = self.parse_hex4()?;
+                let code = if (0xD800..0xDC00).contains(&unit) {
+                    // A high surrogate must be followed by an escaped low surrogate.
+                    self.expect('\\', "after high surrogate")?;
+                    self.expect('u', "after high surrogate")?;
+                    let low = self.parse_hex4()?;
+                    if !(0xDC00..0xE000).contains(&low) {
+                        return Err(self.error("invalid low surrogate"));
+                    }
+                    0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00)
+                } else {
+                    unit
+                };
+                char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+            }
+            Some(c) => Err(self.error(format!("invalid escape '\\{c}'"))),
+            None => Err(self.error("unterminated string")),
+        }
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, JsonError> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let digit = self.peek().and_then(|c| c.to_digit(16))
+                .ok_or_else(|| self.error("expected four hex digits"))?;
+            self.next();
+            code = code * 16 + digit;
+        }
+        Ok(code)
+    }
+
+    fn parse_array(&mut self) -> Result<Json, JsonError> {
+        self.expect('[', "at start of array")?;
+        let mut elements = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.next();
+            return Ok(Json::Array(elements));
+        }
+        loop {
+            elements.push(self.parse_value()?);
+            self.skip_whitespace();
+            if self.peek() == Some(',') {
+                self.next();
+            } else {
+                self.expect(']', "at end of array")?;
+                return Ok(Json::Array(elements));
+            }
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Json, JsonError> {
+        self.expect('{', "at start of object")?;
+        let mut fields = HashMap::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.next();
+            return Ok(Json::Object(Box::new(fields)));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(':', "after object key")?;
+            let value = self.parse_value()?;
+            fields.insert(key, value);
+            self.skip_whitespace();
+            if self.peek() == Some(',') {
+                self.next();
+            } else {
+                self.expect('}', "at end of object")?;
+                return Ok(Json::Object(Box::new(fields)));
+            }
+        }
+    }
+}
+
+#[test]
+fn json_parse_values() {
+    assert_eq!(parse(" null "), Ok(Json::Null));
+    assert_eq!(parse("[true, false, -1.5e2, 0]"),
+               Ok(json!([true, false, (-150.0), 0])));
+    assert_eq!(parse(r#""tab\t quote\" snowman☃ clef𝄞""#),
+               Ok(json!("tab\t quote\" snowman\u{2603} clef\u{1d11e}")));
+    assert_eq!(parse(r#"{"a": {}, "b": [[]]}"#), Ok(json!({"a": {}, "b": [[]]})));
+
+    let value = json!({"text": "line\nbreak \\ \u{1}", "n": [1, 2.5, null]});
+    assert_eq!(parse(&value.to_string()), Ok(value));
+}
+
+#[test]
+fn json_parse_errors() {
+    let error = parse("[\n  \"abc").unwrap_err();
+    assert_eq!(error.to_string(), "unterminated string (2:7)");
+
+    let error = parse("[1, 2 3]").unwrap_err();
+    assert_eq!(error.to_string(), "expected ']' at end of array (1:7)");
+
+    assert_eq!(parse("{\"a\" 1}").unwrap_err().message, "expected ':' after object key");
+    assert_eq!(parse("nul").unwrap_err().message, "expected 'null'");
+    assert_eq!(parse("1 2").unwrap_err().message, "unexpected '2' after JSON value");
+    assert_eq!(parse("").unwrap_err().message, "unexpected end of input");
+}
+
+#[test]
+fn json_parse_numbers() {
+    assert_eq!(parse("0"), Ok(Json::Number(0.0)));
+    assert_eq!(parse("-0.25"), Ok(Json::Number(-0.25)));
+    assert_eq!(parse("12e3"), Ok(Json::Number(12000.0)));
+    assert_eq!(parse("1.5E-2"), Ok(Json::Number(0.015)));
+    assert_eq!(parse("[10,-7]"), Ok(Json::Array(vec![Json::Number(10.0), Json::Number(-7.0)])));
+
+    for (input, message) in [("01", "invalid number \"01\""),
+                             ("-012", "invalid number \"-012\""),
+                             ("1.", "invalid number \"1.\""),
+                             ("-", "invalid number \"-\""),
+                             ("-.5", "invalid number \"-\""),
+                             ("2e", "invalid number \"2e\""),
+                             ("2e+", "invalid number \"2e+\"")] {
+        assert_eq!(parse(input).unwrap_err().message, message, "parsing {input:?}");
+    }
+
+    let error = parse("[1, 1.]").unwrap_err();
+    assert_eq!(error.to_string(), "invalid number \"1.\" (1:5)");
+
+    // Things `f64::from_str` accepts, but JSON doesn't.
+    assert!(parse("+1").is_err());
+    assert!(parse(".5").is_err());
+    assert!(parse("1.e5").is_err());
+}
+
+#[test]
+fn json_parse_depth() {
+    let nested = |depth: usize| "[".repeat(depth) + &"]".repeat(depth);
+    assert!(parse(&nested(MAX_DEPTH)).is_ok());
+
+    let error = parse(&nested(MAX_DEPTH + 1)).unwrap_err();
+    assert_eq!(error.message, format!("nested more than {MAX_DEPTH} levels deep"));
+    assert_eq!((error.line, error.column), (1, MAX_DEPTH + 1));
+
+    // Deep enough to overflow the stack, if the limit weren't there.
+    let deep_objects = "{\"a\":".repeat(1_000_000) + "null" + &"}".repeat(1_000_000);
+    assert!(parse(&deep_objects).is_err());
+}
@@ -6,14 +6,8 @@
 use json::Json;
 
 
-fn main() {
-    println!("Hello, world!");
-
-    let v = my_vec![1,2,3];
-
-    println!("{:?}", v);
-
-    let students_macro = json!([
+fn students() -> Json {
+    json!([
         {
             "name" : "Jim Blandy",
             "class_of": 1926,
@@ -24,7 +18,17 @@ fn main() {
             "class_of": 1702,
             "major": "Knots"
         }
-    ]);
+    ])
+}
+
+fn main() {
+    println!("Hello, world!");
+
+    let v = my_vec![1,2,3];
+
+    println!("{:?}", v);
+
+    let students_macro = students();
 
     let students_hand_coded = Json::Array(vec![
         Json::Object(Box::new(vec![
@@ -40,4 +44,60 @@ fn main() {
     ]);
 
     assert_eq!(students_macro, students_hand_coded);
+    let pretty = students_macro.to_pretty_string(4);
+    println!("{pretty}");
+    match json::parse(&pretty) {
+        Ok(parsed) => assert_eq!(parsed, students_macro),
+        Err(error) => println!("error: {error}"),
+    }
+
+    let first = students_macro.at(0);
+    if let (Some(name), Some(class_of)) = (first.and_then(|s| s.get("name")).and_then(Json::as_str),
+                                           first.and_then(|s| s.get("class_of")).and_then(Json::as_f64)) {
+        println!("{name}, class of {class_of}");
+    }
+}
+
+#[test]
+fn test_json_accessors() {
+    let students = students();
+
+    let class_of = students.at(1).and_then(|student| student.get("class_of"));
+    assert_eq!(class_of.and_then(Json::as_f64), Some(1702.0));
+    assert_eq!(students.at(0).and_then(|student| student.get("major")).and_then(Json::as_str),
+               Some("Tibetan throat singing"));
+
+    assert_eq!(students.at(2), None);
+    assert_eq!(students.get("name"), None);
+    assert_eq!(students.at(0).and_then(|student| student.get("age")), None);
+    assert_eq!(students.at(0).and_then(|student| student.get("name")).and_then(Json::as_f64),
+               None);
+}
+
+#[test]
+fn test_json_pretty() {
+    let expected = r#"[
+  {
+    "class_of": 1926,
+    "major": "Tibetan throat singing",
+    "name": "Jim Blandy"
+  },
+  {
+    "class_of": 1702,
+    "major": "Knots",
+    "name": "Jason Orendorff"
+  }
+]"#;
+    assert_eq!(students().to_pretty_string(2), expected);
+
+    assert_eq!(json!({"empty": [], "nested": {}}).to_pretty_string(4),
+               "{\n    \"empty\": [],\n    \"nested\": {}\n}");
+    assert_eq!(json!(1926).to_pretty_string(2), "1926");
+}
+
+#[test]
+fn test_json_parse_round_trip() {
+    let students = students();
+    assert_eq!(json::parse(&students.to_string()), Ok(students.clone()));
+    assert_eq!(json::parse(&students.to_pretty_string(4)), Ok(students));
 }
@@ -25,8 +25,9 @@ fn main() {
 
     let args: Vec<String> = env::args().collect();
 
-    if args.len() != 5 {
-        eprintln!("Usage: {} FILE PIXELS UPPERLEFT LOWERRIGHT", args[0]);
+    let color = args.len() == 6 && args[5] == "color";
+    if args.len() != 5 && !color {
+        eprintln!("Usage: {} FILE PIXELS UPPERLEFT LOWERRIGHT [color]", args[0]);
         std::process::exit(1);
     }
 
@@ -46,40 +47,17 @@ fn main() {
     // to fill it with computed grayscale values, even while pixels remains the vector's owner.
     // render(&mut pixels, bounds, upper_left, lower_right);
 
-    let threads = 8;
-    let rows_per_band = bounds.1 / threads + 1;
-
-    {
-        // The buffer's chuks_mut method returns an iterator producing mutable, nonoverlapping slices
-        // of the buffer, each of which encloses rows_per_band * bounds.0 pixels - in other words,
-        // rows_per_band complete rows of pixels.
-        let bands: Vec<&mut [u8]> = pixels.chunks_mut(rows_per_band * bounds.0).collect();
-        //crossbeam::scope calls the closure, passing as the spawner argument a value the closure can
-        //use to create new threads. The crossbeam::scope function waits for all such threads to
-        //finish execution before returning itself.
-        crossbeam::scope(|spawner| {
-            // The into_iter() iterator gives each iteration of the loop body exclusive ownership of
-            // one band, ensuring that only one thread can write to it at a time.
-            for (i, band) in bands.into_iter().enumerate() {
-                let top = rows_per_band * i;
-                let height = band.len() / bounds.0;
-                let band_bounds = (bounds.0, height);
-                let band_upper_left = pixel_to_point(bounds, (0, top), upper_left, lower_right);
-                let band_lower_right = pixel_to_point(bounds, (bounds.0, top + height),
-                                                                  upper_left, lower_right);
-                // The move keyword at the front indicates that this closure takes ownership of the
-                // variables it uses; in particular, only the closure may use the mutable slice band.
-                // The argument list |_| means that the closure takes one argument, which it doesn't
-                //use(another spawner for making nested threads).
-                spawner.spawn(move |_| {
-                    render(band, band_bounds, band_upper_left, band_lower_right);
-                });
-            }
-        }).unwrap();
+    if color {
+        let mut pixels = vec![0; bounds.0 * bounds.1 * 3];
+        render_color(&mut pixels, bounds, upper_left, lower_right);
+        write_image(&args[1], &pixels, bounds, ColorType::RGB(8))
+            .expect("error writing PNG file");
+        return;
     }
 
+    render_concurrent(&mut pixels, bounds, upper_left, lower_right, 8);
 
-    write_image(&args[1], &pixels, bounds)
+    write_image(&args[1], &pixels, bounds, ColorType::Gray(8))
         .expect("error writing PNG file");
 }
 
@@ -130,10 +108,7 @@ fn parse_pair<T: FromStr>(s: &str, separator: char) -> Option<(T, T)> {
 
 /// Parse a pair of floating-point numbers separated by a comma as a complex number
 fn parse_complex(s:&str) -> Option<Complex<f64>> {
-    match parse_pair(s, ',') {
-        Some((re, im)) => Some(Complex{re, im}),
-        None => None
-    }
+    parse_pair::<f64>(s, ',').map(|(re, im)| Complex{re, im})
 }
 
 #[test]
@@ -155,7 +130,9 @@ fn test_parse_complex(){
     assert_eq!(parse_complex("1.25,-0.0625"),
                 Some(Complex{re:1.25, im:-0.0625}));
 
+    assert_eq!(parse_complex(",-0.0625"), None);
     assert_eq!(parse_complex(", -0.0625"), None);
+    assert_eq!(parse_complex("1.25x-0.0625"), None);
 }
 
 
@@ -164,7 +141,7 @@ fn test_parse_complex(){
 ///
 /// `bounds` is a pair giving the width and height of the image in pixels.
 /// `pixel` is a (column,row) pair indicating a particular pixel in that image.
-/// The `upper_left` and lower_left` parameters are points on the complex plane designating the
+/// The `upper_left` and `lower_right` parameters are points on the complex plane designating the
 /// area our image covers.
 fn pixel_to_point(bounds:(usize, usize),
                   pixel:(usize, usize),
@@ -187,7 +164,17 @@ fn test_pixel_to_point() {
     assert_eq!(pixel_to_point((100,200), (25,175),
                               Complex{re:-1.0, im:1.0},
                               Complex{re:1.0, im:-1.0}),
-               Complex{re:-0.5, im:-0.75})
+               Complex{re:-0.5, im:-0.75});
+
+    // The corners of the image map to the corners of the region.
+    assert_eq!(pixel_to_point((100,200), (0,0),
+                              Complex{re:-1.0, im:1.0},
+                              Complex{re:1.0, im:-1.0}),
+               Complex{re:-1.0, im:1.0});
+    assert_eq!(pixel_to_point((100,200), (100,200),
+                              Complex{re:-1.0, im:1.0},
+                              Complex{re:1.0, im:-1.0}),
+               Complex{re:1.0, im:-1.0});
 }
 
 
@@ -204,11 +191,30 @@ fn render(pixels: &mut[u8],
 {
     assert!(pixels.len() == bounds.0 * bounds.1);
 
-    for row in 0..bounds.1 {
+    render_band(pixels, bounds, 0, upper_left, lower_right);
+}
+
+/// Render the rows of the image starting at row `top` into `band`, which holds some whole number of
+/// rows. Each pixel is mapped using the bounds of the entire image, so a band comes out exactly as
+/// those same rows would in a call to `render` on the whole image.
+fn render_band(band: &mut [u8],
+               bounds: (usize, usize),
+               top: usize,
+               upper_left: Complex<f64>,
+               lower_right: Complex<f64>)
+{
+    // A zero-width image has no pixels to render, and would make the row count below divide by
+    // zero.
+    if bounds.0 == 0 {
+        return;
+    }
+    assert_eq!(band.len() % bounds.0, 0);
+
+    for row in 0..band.len() / bounds.0 {
         for column in 0..bounds.0 {
-            let point = pixel_to_point(bounds, (column, row),
+            let point = pixel_to_point(bounds, (column, top + row),
                                                     upper_left, lower_right);
-            pixels[row*bounds.0 + column] = match escape_time(point, 255) {
+            band[row*bounds.0 + column] = match escape_time(point, 255) {
                 None => 0,
                 Some(count) => 255 - count as u8
             };
@@ -216,12 +222,161 @@ fn render(pixels: &mut[u8],
     }
 }
 
+/// Like `render`, but split the image into `threads` horizontal bands and render each on its own
+/// thread.
+fn render_concurrent(pixels: &mut [u8],
+                     bounds: (usize, usize),
+                     upper_left: Complex<f64>,
+                     lower_right: Complex<f64>,
+                     threads: usize)
+{
+    if threads <= 1 {
+        render(pixels, bounds, upper_left, lower_right);
+        return;
+    }
+
+    assert!(pixels.len() == bounds.0 * bounds.1);
+    if pixels.is_empty() {
+        return;
+    }
+
+    let rows_per_band = bounds.1 / threads + 1;
+
+    // The buffer's chuks_mut method returns an iterator producing mutable, nonoverlapping slices
+    // of the buffer, each of which encloses rows_per_band * bounds.0 pixels - in other words,
+    // rows_per_band complete rows of pixels.
+    let bands: Vec<&mut [u8]> = pixels.chunks_mut(rows_per_band * bounds.0).collect();
+    //crossbeam::scope calls the closure, passing as the spawner argument a value the closure can
+    //use to create new threads. The crossbeam::scope function waits for all such threads to
+    //finish execution before returning itself.
+    crossbeam::scope(|spawner| {
+        // The into_iter() iterator gives each iteration of the loop body exclusive ownership of
+        // one band, ensuring that only one thread can write to it at a time.
+        for (i, band) in bands.into_iter().enumerate() {
+            let top = rows_per_band * i;
+            // The move keyword at the front indicates that this closure takes ownership of the
+            // variables it uses; in particular, only the closure may use the mutable slice band.
+            // The argument list |_| means that the closure takes one argument, which it doesn't
+            //use(another spawner for making nested threads).
+            spawner.spawn(move |_| {
+                render_band(band, bounds, top, upper_left, lower_right);
+            });
+        }
+    }).unwrap();
+}
+
+#[test]
+fn test_render_concurrent() {
+    let bounds = (97, 61);
+    let upper_left = Complex{re: -1.20, im: 0.35};
+    let lower_right = Complex{re: -1.0, im: 0.20};
+
+    let mut expected = vec![0; bounds.0 * bounds.1];
+    render(&mut expected, bounds, upper_left, lower_right);
+
+    for threads in [0, 1, 3, 8, 100] {
+        let mut pixels = vec![0; bounds.0 * bounds.1];
+        render_concurrent(&mut pixels, bounds, upper_left, lower_right, threads);
+        assert!(pixels == expected, "{threads} threads");
+    }
+}
+
+#[test]
+fn test_render_empty() {
+    let upper_left = Complex{re: -1.0, im: 1.0};
+    let lower_right = Complex{re: 1.0, im: -1.0};
+
+    for bounds in [(0, 0), (0, 10), (10, 0)] {
+        render(&mut [], bounds, upper_left, lower_right);
+        render_concurrent(&mut [], bounds, upper_left, lower_right, 4);
+    }
+}
+
+#[test]
+fn test_render() {
+    let bounds = (5, 5);
+    let mut pixels = vec![0; bounds.0 * bounds.1];
+    render(&mut pixels, bounds, Complex{re: -2.0, im: 2.0}, Complex{re: 3.0, im: -3.0});
+
+    assert_eq!(pixels.len(), 25);
+    // The pixel at (2, 2) maps to the origin, which is in the set, and so is painted black. The
+    // upper-left corner is far outside the set, and escapes almost immediately.
+    assert_eq!(pixels[2 * bounds.0 + 2], 0);
+    assert_eq!(pixels[0], 254);
+}
+
+/// Render a rectangle of the Mandelbrot set like `render`, but in color: `pixels` holds three bytes,
+/// red, green and blue, per pixel. Points in the set are black; points outside it get a hue chosen
+/// by how quickly they escape.
+fn render_color(pixels: &mut [u8],
+                bounds: (usize, usize),
+                upper_left: Complex<f64>,
+                lower_right: Complex<f64>)
+{
+    assert!(pixels.len() == bounds.0 * bounds.1 * 3);
+
+    for row in 0..bounds.1 {
+        for column in 0..bounds.0 {
+            let point = pixel_to_point(bounds, (column, row), upper_left, lower_right);
+            let offset = (row * bounds.0 + column) * 3;
+            pixels[offset..offset + 3].copy_from_slice(&escape_color(escape_time(point, 255), 255));
+        }
+    }
+}
+
+/// Map an `escape_time` result to an RGB color: black for `None`, and otherwise a hue that sweeps
+/// once around the color wheel as `count` goes from zero to `limit`.
+fn escape_color(escape: Option<usize>, limit: usize) -> [u8; 3] {
+    match escape {
+        None => [0, 0, 0],
+        Some(count) => hsv_to_rgb(360.0 * count as f64 / limit as f64, 1.0, 1.0),
+    }
+}
+
+/// Convert a color given as hue (in degrees), saturation and value (both from 0 to 1) to RGB.
+fn hsv_to_rgb(hue: f64, saturation: f64, value: f64) -> [u8; 3] {
+    let chroma = value * saturation;
+    let sector = (hue / 60.0).rem_euclid(6.0);
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = value - chroma;
+    let scale = |c: f64| ((c + m) * 255.0).round() as u8;
+    [scale(r), scale(g), scale(b)]
+}
+
+#[test]
+fn test_render_color() {
+    let bounds = (5, 5);
+    let mut pixels = vec![0; bounds.0 * bounds.1 * 3];
+    render_color(&mut pixels, bounds, Complex{re: -2.0, im: 2.0}, Complex{re: 3.0, im: -3.0});
+
+    // The pixel at (2, 2) is the origin, inside the set; the upper-left corner is outside it.
+    let center = (2 * bounds.0 + 2) * 3;
+    assert_eq!(&pixels[center..center + 3], &[0, 0, 0]);
+    assert_ne!(&pixels[0..3], &[0, 0, 0]);
+
+    assert_eq!(hsv_to_rgb(0.0, 1.0, 1.0), [255, 0, 0]);
+    assert_eq!(hsv_to_rgb(120.0, 1.0, 1.0), [0, 255, 0]);
+    assert_eq!(hsv_to_rgb(240.0, 1.0, 1.0), [0, 0, 255]);
+}
+
 use image::ColorType;
 use image::png::PNGEncoder;
 use std::fs::File;
 
 /// Write the buffer `pixels`, whose dimensions are given by `bounds`, to the file named `filename`
-fn write_image(filename: &str, pixels : &[u8], bounds: (usize, usize)) -> Result<(), std::io::Error>
+///
+/// `color` says how `pixels` is laid out: `ColorType::Gray(8)` for `render`'s one byte per pixel,
+/// or `ColorType::RGB(8)` for `render_color`'s three.
+fn write_image(filename: &str, pixels : &[u8], bounds: (usize, usize), color: ColorType)
+    -> Result<(), std::io::Error>
 {
     // To properly handle File::create's result, we need to match on its return value, as below:
     // let output = match File::create(filename) {
@@ -237,7 +392,7 @@ fn write_image(filename: &str, pixels : &[u8], bounds: (usize, usize)) -> Result
     let encoder= PNGEncoder::new(output);
     encoder.encode(pixels,
                     bounds.0 as u32, bounds.1 as u32,
-                    ColorType::Gray(8))?;
+                    color)?;
 
     Ok(())
 }
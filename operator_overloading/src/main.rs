@@ -60,6 +60,108 @@ impl <T> Mul for Complex<T>
     }
 }
 
+impl<T> Complex<T> {
+    /// The complex conjugate: the same real part, with the imaginary part negated.
+    pub fn conjugate(self) -> Complex<T> where T: Neg<Output=T> {
+        Complex { re: self.re, im: -self.im }
+    }
+
+    /// The square of the distance from the origin. Unlike `norm`, this needs no square root, so it
+    /// works for any numeric type, and is the cheaper choice when only comparing magnitudes.
+    pub fn norm_sqr(self) -> T where T: Mul<Output=T> + Add<Output=T> + Copy {
+        self.re * self.re + self.im * self.im
+    }
+}
+
+impl Complex<f64> {
+    /// The distance from the origin, also called the absolute value or modulus.
+    pub fn norm(self) -> f64 {
+        self.norm_sqr().sqrt()
+    }
+}
+
+#[test]
+fn test_norm() {
+    let z = Complex { re: 3.0, im: 4.0 };
+    assert_eq!(z.norm_sqr(), 25.0);
+    assert_eq!(z.norm(), 5.0);
+    assert_eq!(Complex { re: -3, im: 4 }.norm_sqr(), 25);
+}
+
+use std::ops::Div;
+
+/// Dividing by `rhs` is multiplying by its conjugate and then dividing by the real number
+/// `rhs.re² + rhs.im²`. Writing out the product of `self` and the conjugate by hand spares us a
+/// `Neg` bound, so this works for any T with the four arithmetic operators.
+impl<T> Div for Complex<T>
+    where T: Add<Output=T> + Sub<Output=T> + Mul<Output=T> + Div<Output=T> + Copy
+{
+    type Output = Self;
+    fn div(self, rhs: Complex<T>) -> Self {
+        let denominator = rhs.re * rhs.re + rhs.im * rhs.im;
+        Complex {
+            re: (self.re * rhs.re + self.im * rhs.im) / denominator,
+            im: (self.im * rhs.re - self.re * rhs.im) / denominator,
+        }
+    }
+}
+
+#[test]
+fn test_div() {
+    let a = Complex { re: 1.5, im: -2.25 };
+    let b = Complex { re: -0.5, im: 4.0 };
+
+    assert!(((a / b) * b).approx_eq(&a, 1e-12));
+    assert!(((b / a) * a).approx_eq(&b, 1e-12));
+    assert!((a / a).approx_eq(&Complex { re: 1.0, im: 0.0 }, 1e-12));
+
+    assert_eq!(b.conjugate(), Complex { re: -0.5, im: -4.0 });
+    assert_eq!(Complex { re: 6, im: 8 } / Complex { re: 3, im: 4 }, Complex { re: 2, im: 0 });
+}
+
+/// The right operand of an operator doesn't have to share the type of the left one. Scaling a
+/// complex number by a real one is common enough to deserve its own impl, rather than making the
+/// caller wrap the scalar in a Complex with a zero imaginary part.
+impl Mul<f64> for Complex<f64> {
+    type Output = Complex<f64>;
+    fn mul(self, rhs: f64) -> Complex<f64> {
+        Complex { re: self.re * rhs, im: self.im * rhs }
+    }
+}
+
+/// Rust never swaps the operands for you, so `2.0 * z` needs an impl of its own, on `f64`.
+impl Mul<Complex<f64>> for f64 {
+    type Output = Complex<f64>;
+    fn mul(self, rhs: Complex<f64>) -> Complex<f64> {
+        rhs * self
+    }
+}
+
+#[test]
+fn test_scalar_mul() {
+    let z = Complex { re: 2.0, im: 3.0 };
+    assert_eq!(z * 2.0, Complex { re: 4.0, im: 6.0 });
+    assert_eq!(2.0 * z, Complex { re: 4.0, im: 6.0 });
+    assert_eq!(2.0 * z, z * 2.0);
+}
+
+/// Floating-point arithmetic rounds, so two `Complex<f64>` values computed along different paths
+/// are rarely exactly equal. Compare them component-wise within a tolerance instead.
+impl Complex<f64> {
+    fn approx_eq(&self, other: &Complex<f64>, epsilon: f64) -> bool {
+        (self.re - other.re).abs() <= epsilon && (self.im - other.im).abs() <= epsilon
+    }
+}
+
+#[test]
+fn test_approx_eq() {
+    let a = Complex { re: 1.0, im: -2.0 };
+    let b = Complex { re: 1.0 + 1e-12, im: -2.0 - 1e-12 };
+
+    assert!(a.approx_eq(&b, 1e-6));
+    assert!(!a.approx_eq(&b, 1e-15));
+}
+
 trait Negate {
     type Output;
     fn neg(self) -> Self::Output;
@@ -147,7 +249,7 @@ trait PartialOrdExample<Rhs=Self> : PartialEq<Rhs>
 
 
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 struct Interval<T> {
     lower: T, //inclusive
     upper: T, // exclusive
@@ -155,12 +257,71 @@ struct Interval<T> {
 
 use std::cmp::{PartialOrd};
 
+/// PartialOrd<Rhs> requires PartialEq<Rhs>, so comparing intervals with different element types
+/// needs an equality impl that is just as general. `#[derive(PartialEq)]` would only compare
+/// intervals of the same type.
+impl<T, U> PartialEq<Interval<U>> for Interval<T> where T: PartialEq<U> {
+    fn eq(&self, other: &Interval<U>) -> bool {
+        self.lower == other.lower && self.upper == other.upper
+    }
+}
+
+/// A struct literal like `Interval { lower: 10, upper: 5 }` happily builds a reversed interval,
+/// which the comparison below can't make sense of. `new` orders the bounds so that
+/// `lower <= upper` always holds.
+impl<T: PartialOrd> Interval<T> {
+    pub fn new(a: T, b: T) -> Interval<T> {
+        if a <= b {
+            Interval { lower: a, upper: b }
+        } else {
+            Interval { lower: b, upper: a }
+        }
+    }
+
+    /// The upper bound is exclusive, so an interval whose bounds coincide holds no elements.
+    pub fn is_empty(&self) -> bool {
+        self.lower == self.upper
+    }
+
+    /// True if `value` lies in the interval: at or above the lower bound, and below the upper.
+    pub fn contains(&self, value: &T) -> bool {
+        self.lower <= *value && *value < self.upper
+    }
+
+    /// The distance between the bounds.
+    pub fn length(&self) -> T where T: Sub<Output=T> + Copy {
+        self.upper - self.lower
+    }
+}
+
+#[test]
+fn test_interval_contains() {
+    let interval = Interval::new(10, 20);
+    assert!(interval.contains(&10));
+    assert!(interval.contains(&15));
+    assert!(!interval.contains(&20));
+    assert!(!interval.contains(&9));
+    assert_eq!(interval.length(), 10);
+
+    assert!(!Interval::new(3, 3).contains(&3));
+    assert_eq!(Interval::new(2.5, 1.0).length(), 1.5);
+}
+
+#[test]
+fn test_interval_new() {
+    assert_eq!(Interval::new(10, 5), Interval { lower: 5, upper: 10 });
+    assert_eq!(Interval::new(5, 10), Interval { lower: 5, upper: 10 });
+
+    assert!(Interval::new(3, 3).is_empty());
+    assert!(!Interval::new(3, 4).is_empty());
+}
+
 /// We would like to make values of Interval type partially ordered: one interval is less than another
 /// if it falls entirely before the other, with no overlap. If two unequal intervals overlap, they're
 /// unordered: some element of each side is less than some element of the other. And two equal
 /// intervals are simply equal.
-impl <T: PartialOrd> PartialOrd<Interval<T>> for Interval<T> {
-    fn partial_cmp(&self, other: &Interval<T>) -> Option<Ordering> {
+impl<T, U> PartialOrd<Interval<U>> for Interval<T> where T: PartialOrd<U> {
+    fn partial_cmp(&self, other: &Interval<U>) -> Option<Ordering> {
         if self == other {
             Some(Ordering::Equal)
         } else if self.lower >= other.upper {
@@ -173,6 +334,35 @@ impl <T: PartialOrd> PartialOrd<Interval<T>> for Interval<T> {
     }
 }
 
+#[test]
+fn test_interval_mixed_types() {
+    // The standard library doesn't let an i32 be compared with an i64 directly, so wrap one side
+    // in a type that knows how to widen itself.
+    #[derive(Debug, PartialEq, PartialOrd)]
+    struct Narrow(i32);
+
+    impl PartialEq<i64> for Narrow {
+        fn eq(&self, other: &i64) -> bool {
+            i64::from(self.0) == *other
+        }
+    }
+
+    impl PartialOrd<i64> for Narrow {
+        fn partial_cmp(&self, other: &i64) -> Option<Ordering> {
+            i64::from(self.0).partial_cmp(other)
+        }
+    }
+
+    let narrow = Interval { lower: Narrow(10), upper: Narrow(20) };
+
+    assert!(narrow < Interval { lower: 20_i64, upper: 5_000_000_000 });
+    assert!(narrow > Interval { lower: -5_000_000_000_i64, upper: 10 });
+    assert!(narrow == Interval { lower: 10_i64, upper: 20 });
+
+    let overlapping = Interval { lower: 15_i64, upper: 5_000_000_000 };
+    assert_eq!(narrow.partial_cmp(&overlapping), None);
+}
+
 /// Index and IndexMut
 /// You can specify how an indexing expression like a[i] works on your type by implementing the
 /// std::ops::Index and std::ops::IndexMut traits. Arrays support the [] operator directly, but on
@@ -198,7 +388,7 @@ trait IndexExample<Idx> {
     fn index(&self, index: Idx) -> &Self::Output;
 }
 
-trait IndexMutExample<Idx> {
+trait IndexMutExample<Idx>: IndexExample<Idx> {
     fn index_mut(&mut self, index: Idx) -> &mut Self::Output;
 }
 
@@ -216,21 +406,65 @@ impl <P: Default + Copy> Image<P> {
     }
 }
 
+/// Indexing with `image[row][col]` panics when either coordinate is out of range, just as slice
+/// indexing does. `get` and `get_mut` are the non-panicking alternatives, in the same spirit as
+/// the slice methods of the same name.
+impl<P> Image<P> {
+    fn height(&self) -> usize {
+        self.pixels.len().checked_div(self.width).unwrap_or(0)
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&P> {
+        if row >= self.height() || col >= self.width {
+            return None;
+        }
+        self.pixels.get(row * self.width + col)
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut P> {
+        if row >= self.height() || col >= self.width {
+            return None;
+        }
+        self.pixels.get_mut(row * self.width + col)
+    }
+}
+
 impl<P> std::ops::Index<usize> for Image<P> {
     type Output = [P];
     fn index(&self, index: usize) -> &Self::Output {
-        let start = row*self.width;
+        let start = index * self.width;
         &self.pixels[start..start + self.width]
     }
 }
 
 impl<P> std::ops::IndexMut<usize> for Image<P> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        let start = row * self.width;
+        let start = index * self.width;
         &mut self.pixels[start..start + self.width]
     }
 }
 
+#[test]
+fn test_image_indexing() {
+    let mut image = Image::<u8>::new(4, 3);
+    image[1][2] = 7;
+    image[2][3] = 9;
+
+    assert_eq!(image[1], [0, 0, 7, 0]);
+    assert_eq!(image.get(1, 2), Some(&7));
+    assert_eq!(image.get(2, 3), Some(&9));
+    assert_eq!(image.get(0, 0), Some(&0));
+
+    // A column past the width must not wrap around into the next row.
+    assert_eq!(image.get(1, 4), None);
+    assert_eq!(image.get(3, 0), None);
+
+    *image.get_mut(0, 1).unwrap() = 5;
+    assert_eq!(image[0][1], 5);
+    assert!(image.get_mut(0, 4).is_none());
+    assert!(image.get_mut(3, 3).is_none());
+}
+
 fn main() {
     println!("Hello, world!");
 
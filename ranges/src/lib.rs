@@ -8,10 +8,88 @@ use std::ops::Range;
 /// If either range is empty, they don't count as overlapping.
 ///
 ///     assert_eq!(ranges::overlap(0..0, 0..10), false);
-pub fn overlap(r1: Range<usize>, r2: Range<usize>) -> bool {
+///
+/// The bounds can be of any ordered type, not just `usize`:
+///
+///     assert_eq!(ranges::overlap(-10i64..-2, -5..5), true);
+///     assert_eq!(ranges::overlap('a'..'m', 'm'..'z'), false);
+pub fn overlap<T: Ord>(r1: Range<T>, r2: Range<T>) -> bool {
     r1.start < r1.end && r2.start < r2.end && r1.start < r2.end && r2.start < r1.end
 }
 
+/// Return the region two ranges have in common, if they overlap.
+///
+///     assert_eq!(ranges::intersection(0..7, 3..10), Some(3..7));
+///     assert_eq!(ranges::intersection(1..5, 101..105), None);
+///
+/// Empty ranges never overlap anything, so they have no intersection either.
+///
+///     assert_eq!(ranges::intersection(0..0, 0..10), None);
+pub fn intersection(r1: Range<usize>, r2: Range<usize>) -> Option<Range<usize>> {
+    if !overlap(r1.clone(), r2.clone()) {
+        return None;
+    }
+    Some(r1.start.max(r2.start)..r1.end.min(r2.end))
+}
+
+/// Clamp a range so that it lies within `0..bound`. Both ends are capped at `bound`.
+///
+///     assert_eq!(ranges::clamp(2..5, 10), 2..5);
+///     assert_eq!(ranges::clamp(8..15, 10), 8..10);
+///
+/// An inverted range collapses to an empty range at its (clamped) start.
+///
+///     assert_eq!(ranges::clamp(7..3, 10), 7..7);
+///     assert_eq!(ranges::clamp(12..3, 10), 10..10);
+pub fn clamp(r: Range<usize>, bound: usize) -> Range<usize> {
+    let start = r.start.min(bound);
+    let end = r.end.min(bound).max(start);
+    start..end
+}
+
+/// Return what is left of `whole` after removing `hole` from it: no ranges if `hole` covers all
+/// of `whole`, two if it punches out the middle, and otherwise one.
+///
+///     assert_eq!(ranges::subtract(0..10, 3..5), vec![0..3, 5..10]);
+///     assert_eq!(ranges::subtract(0..10, 20..30), vec![0..10]);
+pub fn subtract(whole: Range<usize>, hole: Range<usize>) -> Vec<Range<usize>> {
+    let common = match intersection(whole.clone(), hole) {
+        Some(common) => common,
+        None => return if whole.start < whole.end { vec![whole] } else { vec![] },
+    };
+
+    let mut remaining = Vec::new();
+    if whole.start < common.start {
+        remaining.push(whole.start..common.start);
+    }
+    if common.end < whole.end {
+        remaining.push(common.end..whole.end);
+    }
+    remaining
+}
+
+/// Coalesce a list of ranges in place, leaving it sorted by `start` and with no two ranges
+/// overlapping or touching. Empty ranges are dropped.
+///
+///     let mut spans = vec![8..10, 0..3, 2..5, 5..6, 7..7];
+///     ranges::merge(&mut spans);
+///     assert_eq!(spans, vec![0..6, 8..10]);
+pub fn merge(ranges: &mut Vec<Range<usize>>) {
+    ranges.retain(|r| r.start < r.end);
+    ranges.sort_by_key(|r| r.start);
+
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for r in ranges.drain(..) {
+        match merged.last_mut() {
+            Some(last) if overlap(last.clone(), r.clone()) || last.end == r.start => {
+                last.end = last.end.max(r.end);
+            }
+            _ => merged.push(r),
+        }
+    }
+    *ranges = merged;
+}
+
 pub fn add(left: usize, right: usize) -> usize {
     left + right
 }
@@ -30,6 +108,49 @@ mod tests {
         (a - b).abs() < 1e-6
     }
 
+    #[test]
+    fn subtract_without_overlap() {
+        assert_eq!(subtract(0..10, 10..20), vec![0..10]);
+        assert_eq!(subtract(5..10, 0..5), vec![5..10]);
+        assert_eq!(subtract(0..10, 3..3), vec![0..10]);
+    }
+
+    #[test]
+    fn subtract_prefix_and_suffix() {
+        assert_eq!(subtract(0..10, 0..4), vec![4..10]);
+        assert_eq!(subtract(5..10, 0..7), vec![7..10]);
+        assert_eq!(subtract(0..10, 6..10), vec![0..6]);
+        assert_eq!(subtract(0..10, 6..15), vec![0..6]);
+    }
+
+    #[test]
+    fn subtract_middle_and_whole() {
+        assert_eq!(subtract(0..10, 3..5), vec![0..3, 5..10]);
+        assert_eq!(subtract(2..8, 0..10), vec![]);
+        assert_eq!(subtract(2..8, 2..8), vec![]);
+    }
+
+    #[test]
+    fn merge_nested() {
+        let mut spans = vec![0..10, 2..4, 3..8];
+        merge(&mut spans);
+        assert_eq!(spans, vec![0..10]);
+    }
+
+    #[test]
+    fn merge_adjacent_and_disjoint() {
+        let mut spans = vec![20..25, 5..10, 0..5, 12..15];
+        merge(&mut spans);
+        assert_eq!(spans, vec![0..10, 12..15, 20..25]);
+    }
+
+    #[test]
+    fn merge_all_empty() {
+        let mut spans = vec![3..3, Range { start: 7, end: 2 }, 0..0];
+        merge(&mut spans);
+        assert_eq!(spans, vec![]);
+    }
+
     #[test]
     fn trig_works() {
         use std::f64::consts::PI;
@@ -38,114 +159,114 @@ mod tests {
 }
 
 
-/// Whereas crates are about code sharing between projects, modules are about code organization within a
-/// project. They act as Rust's namespaces, containers for the functions, types, constants, and so on
-/// that make up your Rust program or library.
-///
-/// A module is a collection of items, named features like structs, functions. The 'pub' keyword makes
-/// an item public, so it can be accessed from outside the module.
-///
-/// Any item marked 'pub(crate)' means that it is available anywhere inside this crate, but isn't exposed
-/// as part of the external interface. It can't be used by other crates, and it won't show up in this
-/// crate's documentation.
-///
-/// Anything that isn't marked pub is private and can only be used in the same module in which it is
-/// defined, or any child modules. Marking an item pub is often known as exporting that item.
-///
-/// Modules can nest, and it's fairly common to see a module that's just a collection of submodules.
-/// If you want an item in a nested module to be visible to other crates, be sure to make it an all
-/// enclosing modules as public.
-///
-/// It's also possible to specify pub(super), making an item visible to the parent module only, and in
-/// pup(in <path>), which makes it visible in a specific parent module and its descendants.
-///     mod plant_structures {
-///         pub mod roots {
-///             pub mod products {
-///                 pub(in crate::plant_structures::root) struct Cytokinin {
-///                     ...
-///                 }
-///                 use products::Cytokinin;    //ok: in `roots` module.
-///         }
-///         use roots::products::Cytokinin;     //error: `Cytokinin` is private
-///     }
-///
-/// Modules in Separate files or in their own directories
-/// A module can be written like this:
-///     mod spores;
-/// When Rust sees mod spores;', it checks for both spores.rs and spores/mod.rs; if neither file
-/// exists or both exist, that's an error. It's also possible to use a file and directory with the
-/// same name to make up a module.
-///
-/// These three options - modules in their own file, modules in their own directory with a mod.rs,
-/// and modules in their own file with a supplementary directory containing submodules - give the
-/// module system enough flexibility to support almost any project structure you might desire.
-///
-/// Paths and Imports
-///     use std::mem;
-///
-/// The use declaration causes the name mem to be a local alias for std::mem throughout the enclosing
-/// block or module.
-///
-/// Modules donot automatically inherit names from their parent modules. Instead, each module starts
-/// with a blank slate and must import the names it uses.
-/// The keywords `super` and `crate` have a special meaning in paths: super refers to the parent
-/// module, and crate refers to the crate containing the current module.
-/// Using paths relative to the crate root rather than the current module makes it easier to move
-/// code around the project, since all the imports won't break if the path of the current module
-/// changes.
-/// Submodules can access private items in their parent modules with `use super::*`.
-///
-/// Modules aren't the same thing as files, but there is a natural analogy between modules and the
-/// files and directories of a Unix filesystem. The use keyword creates aliases, just as the ln command
-/// creates links. Paths, like filenames, come in absolute and relative forms. self and super are
-/// like the . and .. special directories.
-///
-///
-/// A struct's fields, even private fields are accessible throughout the module where the struct is
-/// declared, and its submodules. Outside the module, only public fields are accessible.
-///
-/// Statics and Constants
-/// The `const` keyword introduces a constant. The `static` keyword introduces a static item.
-///
-/// A constant is a bit like a C++ #define: the value is compiled into your code every place it's
-/// used. A static is a variable that's setup before your program starts running and lasts until it
-/// exits. Use constants for magic numbers and strings in your code. Use statics for larger amounts
-/// of data, or anytime you need to borrow a reference to the constant value.
-///
-/// There are mut constants. Statics can be marked mut, but Rust has no way to enforce its rules
-/// about exclusive access on mut statics. They are, therefore inherently non-thread safe, and safe
-/// code can't use them at all. Rust discourages global mutable state.
-///
-///
-/// Versions
-/// The version compatibility rules are adapted from Semantic versioning [https://semver.org]
-/// * A version number that starts with 0.0 is so raw that Cargo never assumes it's compatible with
-/// any other version.
-/// * A version number that starts with 0.x, where x is nonzero, is considered compatible with other
-///point releases in the 0.x series.
-/// * Once a project reaches 1.0, only new major versions break compatibility. So if you ask for
-/// version 2.0.1, Cargo might use 2.17.99 instead, but not 3.0
-///
-///
-///
-/// Cargo.lock
-/// A Cargo.lock file records the exact version of every crate used in the project. Later builds
-/// will consult this file and continue to use the same versions. Cargo upgrades to newer versions
-/// only when you tell it to, either by manually bumping up the version number in your Cargo.toml
-/// file or by running cargo update.
-///
-/// Cargo.lock is automatically generated for you, and you normally won't edit it by hand. Nonetheless,
-/// if your project is an executable, you should commit Cargo.lock to version control. That way,
-/// everyone who builds your project will consistently get the same versions. The history of your
-/// Cargo.lock file will record your dependency updates.
-///
-/// If your project is an ordinary Rust library, don't bother committing Cargo.lock. Your library's
-/// downstream users will have Cargo.lock files that contain version information for their entire
-/// dependency graph; they will ignore your library's Cargo.lock file. In the rare case that your
-/// project is a shared library (.dll, .dylib or .so file), there is no such downstream cargo user,
-/// and you should therefore commit Cargo.lock.
-///
-/// Cargo.toml's flexible version specifiers make it easy to use Rust libraries in your project and
-/// maximize compatibility among libraries. Cargo.lock's bookkeeping supports consistent, reproducible
-/// builds across machines.
+// Whereas crates are about code sharing between projects, modules are about code organization within a
+// project. They act as Rust's namespaces, containers for the functions, types, constants, and so on
+// that make up your Rust program or library.
+//
+// A module is a collection of items, named features like structs, functions. The 'pub' keyword makes
+// an item public, so it can be accessed from outside the module.
+//
+// Any item marked 'pub(crate)' means that it is available anywhere inside this crate, but isn't exposed
+// as part of the external interface. It can't be used by other crates, and it won't show up in this
+// crate's documentation.
+//
+// Anything that isn't marked pub is private and can only be used in the same module in which it is
+// defined, or any child modules. Marking an item pub is often known as exporting that item.
+//
+// Modules can nest, and it's fairly common to see a module that's just a collection of submodules.
+// If you want an item in a nested module to be visible to other crates, be sure to make it an all
+// enclosing modules as public.
+//
+// It's also possible to specify pub(super), making an item visible to the parent module only, and in
+// pup(in <path>), which makes it visible in a specific parent module and its descendants.
+//     mod plant_structures {
+//         pub mod roots {
+//             pub mod products {
+//                 pub(in crate::plant_structures::root) struct Cytokinin {
+//                     ...
+//                 }
+//                 use products::Cytokinin;    //ok: in `roots` module.
+//         }
+//         use roots::products::Cytokinin;     //error: `Cytokinin` is private
+//     }
+//
+// Modules in Separate files or in their own directories
+// A module can be written like this:
+//     mod spores;
+// When Rust sees mod spores;', it checks for both spores.rs and spores/mod.rs; if neither file
+// exists or both exist, that's an error. It's also possible to use a file and directory with the
+// same name to make up a module.
+//
+// These three options - modules in their own file, modules in their own directory with a mod.rs,
+// and modules in their own file with a supplementary directory containing submodules - give the
+// module system enough flexibility to support almost any project structure you might desire.
+//
+// Paths and Imports
+//     use std::mem;
+//
+// The use declaration causes the name mem to be a local alias for std::mem throughout the enclosing
+// block or module.
+//
+// Modules donot automatically inherit names from their parent modules. Instead, each module starts
+// with a blank slate and must import the names it uses.
+// The keywords `super` and `crate` have a special meaning in paths: super refers to the parent
+// module, and crate refers to the crate containing the current module.
+// Using paths relative to the crate root rather than the current module makes it easier to move
+// code around the project, since all the imports won't break if the path of the current module
+// changes.
+// Submodules can access private items in their parent modules with `use super::*`.
+//
+// Modules aren't the same thing as files, but there is a natural analogy between modules and the
+// files and directories of a Unix filesystem. The use keyword creates aliases, just as the ln command
+// creates links. Paths, like filenames, come in absolute and relative forms. self and super are
+// like the . and .. special directories.
+//
+//
+// A struct's fields, even private fields are accessible throughout the module where the struct is
+// declared, and its submodules. Outside the module, only public fields are accessible.
+//
+// Statics and Constants
+// The `const` keyword introduces a constant. The `static` keyword introduces a static item.
+//
+// A constant is a bit like a C++ #define: the value is compiled into your code every place it's
+// used. A static is a variable that's setup before your program starts running and lasts until it
+// exits. Use constants for magic numbers and strings in your code. Use statics for larger amounts
+// of data, or anytime you need to borrow a reference to the constant value.
+//
+// There are mut constants. Statics can be marked mut, but Rust has no way to enforce its rules
+// about exclusive access on mut statics. They are, therefore inherently non-thread safe, and safe
+// code can't use them at all. Rust discourages global mutable state.
+//
+//
+// Versions
+// The version compatibility rules are adapted from Semantic versioning [https://semver.org]
+// * A version number that starts with 0.0 is so raw that Cargo never assumes it's compatible with
+// any other version.
+// * A version number that starts with 0.x, where x is nonzero, is considered compatible with other
+//point releases in the 0.x series.
+// * Once a project reaches 1.0, only new major versions break compatibility. So if you ask for
+// version 2.0.1, Cargo might use 2.17.99 instead, but not 3.0
+//
+//
+//
+// Cargo.lock
+// A Cargo.lock file records the exact version of every crate used in the project. Later builds
+// will consult this file and continue to use the same versions. Cargo upgrades to newer versions
+// only when you tell it to, either by manually bumping up the version number in your Cargo.toml
+// file or by running cargo update.
+//
+// Cargo.lock is automatically generated for you, and you normally won't edit it by hand. Nonetheless,
+// if your project is an executable, you should commit Cargo.lock to version control. That way,
+// everyone who builds your project will consistently get the same versions. The history of your
+// Cargo.lock file will record your dependency updates.
+//
+// If your project is an ordinary Rust library, don't bother committing Cargo.lock. Your library's
+// downstream users will have Cargo.lock files that contain version information for their entire
+// dependency graph; they will ignore your library's Cargo.lock file. In the rare case that your
+// project is a shared library (.dll, .dylib or .so file), there is no such downstream cargo user,
+// and you should therefore commit Cargo.lock.
+//
+// Cargo.toml's flexible version specifiers make it easy to use Rust libraries in your project and
+// maximize compatibility among libraries. Cargo.lock's bookkeeping supports consistent, reproducible
+// builds across machines.
 
@@ -50,6 +50,8 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     regex();
 
+    unicode_normalization();
+
     Ok(())
 }
 
@@ -76,6 +78,56 @@ fn range() {
     assert_eq!(&full[2..4], "ok");
     assert_eq!(full[..].len(), 11);
     assert_eq!(full[5..].contains("boo"), false);
+    assert_eq!(char_at(full, 4), Some('k'));
+    assert_eq!(truncate_to_bytes(full, 4), "book");
+}
+
+/// Return the character that starts at `byte_index` in `s`: the clumsy chars-iterator dance above,
+/// packaged up. Return `None` if `byte_index` is past the end of `s`, or falls in the middle of a
+/// character's UTF-8 encoding rather than at its start.
+fn char_at(s: &str, byte_index: usize) -> Option<char> {
+    s.get(byte_index..)?.chars().next()
+}
+
+#[test]
+fn test_char_at() {
+    assert_eq!(char_at("bookkeeping", 0), Some('b'));
+    assert_eq!(char_at("bookkeeping", 4), Some('k'));
+    assert_eq!(char_at("bookkeeping", 11), None);
+    assert_eq!(char_at("bookkeeping", 100), None);
+
+    // 'é' starts at byte 3 and takes two bytes in UTF-8, so byte 4 is in the middle of it.
+    let word = "café ça";
+    assert_eq!(char_at(word, 3), Some('é'));
+    assert_eq!(char_at(word, 4), None);
+    assert_eq!(char_at(word, 6), Some('ç'));
+}
+
+/// Return the longest prefix of `s` that is at most `max_bytes` long, for fitting text into a
+/// fixed-width field. Slicing at `max_bytes` directly would panic if that fell inside a multibyte
+/// character, so back up to the nearest character boundary first.
+fn truncate_to_bytes(s: &str, max_bytes: usize) -> &str {
+    if max_bytes >= s.len() {
+        return s;
+    }
+    let mut end = max_bytes;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+#[test]
+fn test_truncate_to_bytes() {
+    assert_eq!(truncate_to_bytes("bookkeeping", 6), "bookke");
+    assert_eq!(truncate_to_bytes("bookkeeping", 0), "");
+
+    // 'é' occupies bytes 3 and 4, so a limit of 4 can't include any of it.
+    assert_eq!(truncate_to_bytes("café ça", 4), "caf");
+    assert_eq!(truncate_to_bytes("café ça", 5), "café");
+
+    assert_eq!(truncate_to_bytes("bookkeeping", 11), "bookkeeping");
+    assert_eq!(truncate_to_bytes("bookkeeping", 100), "bookkeeping");
 }
 
 fn extend() {
@@ -204,6 +256,39 @@ fn split_whitespace() {
     assert_eq!(poem.split_whitespace().collect::<Vec<_>>(),
                 vec!["This", "is", "just", "to", "say", "I", "have", "eaten", "the"
                         , "plums", "again"]);
+    assert_eq!(word_frequencies(poem)["plums"], 1);
+}
+
+use std::collections::HashMap;
+
+/// Count how many times each word occurs in `text`. Words are split on whitespace, lowercased, and
+/// stripped of any leading or trailing punctuation, so "The", "the," and "(the" all count as "the".
+/// Anything with no letters or digits at all, like a lone dash, isn't counted.
+fn word_frequencies(text: &str) -> HashMap<String, u32> {
+    let mut counts = HashMap::new();
+    for word in text.split_whitespace() {
+        let word = word.trim_matches(|c: char| !c.is_alphanumeric());
+        if word.is_empty() {
+            continue;
+        }
+        *counts.entry(word.to_lowercase()).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[test]
+fn test_word_frequencies() {
+    let paragraph = "The plums were delicious -- so sweet, and so cold. \
+                     Forgive me: the PLUMS were in the icebox.";
+    let counts = word_frequencies(paragraph);
+    assert_eq!(counts["the"], 3);
+    assert_eq!(counts["plums"], 2);
+    assert_eq!(counts["so"], 2);
+    assert_eq!(counts["cold"], 1);
+    assert_eq!(counts["icebox"], 1);
+    assert!(!counts.contains_key("--"));
+    assert!(!counts.contains_key(""));
+    assert_eq!(counts.values().sum::<u32>(), 17);
 }
 
 
@@ -296,6 +381,9 @@ fn regex(){
         .collect();
 
     assert_eq!(matches, vec!["1.0.0", "1.0.1-beta", "1.2.4"]);
+
+    let vars = HashMap::from([("version", matches[2])]);
+    assert_eq!(render_template("we settled on {{version}}", &vars), "we settled on 1.2.4");
 }
 
 
@@ -321,6 +409,36 @@ fn regex_from_commandline(){
 
 }
 
+/// Replace each `{{name}}` placeholder in `template` with the value `vars` gives for `name`.
+/// Placeholders whose names `vars` doesn't mention are left as they are, braces and all.
+fn render_template(template: &str, vars: &HashMap<&str, &str>) -> String {
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        static ref PLACEHOLDER: Regex = Regex::new(r"\{\{(\w+)\}\}")
+            .expect("error parsing regex");
+    }
+
+    PLACEHOLDER.replace_all(template, |captures: &regex::Captures| {
+        match vars.get(&captures[1]) {
+            Some(value) => value.to_string(),
+            None => captures[0].to_string(),
+        }
+    }).into_owned()
+}
+
+#[test]
+fn test_render_template() {
+    let vars = HashMap::from([("name", "Ferris"), ("lang", "Rust"), ("a", "1"), ("b", "2")]);
+
+    assert_eq!(render_template("{{name}} writes {{lang}}, and {{name}} likes it.", &vars),
+               "Ferris writes Rust, and Ferris likes it.");
+    assert_eq!(render_template("Hello, {{name}}! Today is {{day}}.", &vars),
+               "Hello, Ferris! Today is {{day}}.");
+    assert_eq!(render_template("{{a}}{{b}}", &vars), "12");
+    assert_eq!(render_template("no placeholders", &vars), "no placeholders");
+}
+
 /// Unicode has two ways to represent the accented text:
 /// * The composed form, where the text is written with accented characters.
 /// * The decomposed form, where the text is written in ascii, without accents and followed by code
@@ -342,4 +460,21 @@ fn regex_from_commandline(){
 fn unicode_normalization() {
     assert!("th\u{e9}" != "the\u{301}");
     assert!("th\u{e9}" > "the\u{301}");
+    assert!(normalized_eq("th\u{e9}", "the\u{301}"));
+}
+
+use unicode_normalization::UnicodeNormalization;
+
+/// Compare `a` and `b` for Unicode equivalence, by comparing their NFC (composed) forms character
+/// by character. This saves collecting either normalized form into a `String`.
+fn normalized_eq(a: &str, b: &str) -> bool {
+    a.nfc().eq(b.nfc())
+}
+
+#[test]
+fn test_normalized_eq() {
+    assert!(normalized_eq("th\u{e9}", "the\u{301}"));
+    assert!(normalized_eq("bookkeeping", "bookkeeping"));
+    assert!(!normalized_eq("th\u{e9}", "the"));
+    assert!(!normalized_eq("bookkeeping", "bookkeeper"));
 }
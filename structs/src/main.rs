@@ -13,6 +13,36 @@ pub struct GrayscaleMap {
     size: (usize, usize)
 }
 
+impl GrayscaleMap {
+    /// Binarize the image: every pixel below `level` becomes black (`0`), everything else white
+    /// (`255`). The size of the map is preserved.
+    pub fn threshold(&self, level: u8) -> GrayscaleMap {
+        let pixels = self.pixels.iter()
+            .map(|&p| if p < level { 0 } else { 255 })
+            .collect();
+        GrayscaleMap { pixels, size: self.size }
+    }
+
+    /// Produce the negative of the image, mapping each pixel `p` to `255 - p`.
+    pub fn invert(&self) -> GrayscaleMap {
+        let pixels = self.pixels.iter().map(|&p| 255 - p).collect();
+        GrayscaleMap { pixels, size: self.size }
+    }
+}
+
+#[test]
+fn test_threshold_and_invert() {
+    let map = new_map((3, 2), vec![0, 50, 127, 128, 200, 255]);
+
+    let binary = map.threshold(128);
+    assert_eq!(binary.size, (3, 2));
+    assert_eq!(binary.pixels, vec![0, 0, 0, 255, 255, 255]);
+
+    let negative = map.invert();
+    assert_eq!(negative.size, (3, 2));
+    assert_eq!(negative.pixels, vec![255, 205, 128, 127, 55, 0]);
+}
+
 fn main() {
 
     let width = 1024;
@@ -1,5 +1,6 @@
 /// A generic struct can also take parameters that are constant values.
 /// A polynomial of degree N - 1.
+#[derive(Clone, Copy)]
 pub(crate) struct Polynomial<const N: usize> {
     /// The coefficients of the polynomial.
     ///
@@ -8,6 +9,8 @@ pub(crate) struct Polynomial<const N: usize> {
     pub (crate)coefficients: [f64; N]
 }
 
+use num::Complex;
+
 /// A const generic parameter may be any integer type, char, or bool. Floating-point numbers, enums,
 /// and other types are not permitted.
 impl<const N: usize> Polynomial<N> {
@@ -25,6 +28,106 @@ impl<const N: usize> Polynomial<N> {
         }
         sum
     }
+
+    /// Evaluate the polynomial at a complex `x`, again by Horner's method. The coefficients stay
+    /// real; only the argument and the running sum are complex. This is what root-finding needs,
+    /// since a real polynomial can have complex roots.
+    pub fn eval_complex(&self, x: Complex<f64>) -> Complex<f64> {
+        let mut sum = Complex::new(0.0, 0.0);
+        for i in (0..N).rev() {
+            sum = x * sum + self.coefficients[i];
+        }
+        sum
+    }
+
+    /// The coefficients of the derivative: the term c·x^i becomes i·c·x^(i-1). Shrinking `N` by one
+    /// isn't expressible with const generics yet, so this returns a `Vec` instead of a Polynomial.
+    pub fn derivative(&self) -> Vec<f64> {
+        self.coefficients.iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, c)| c * i as f64)
+            .collect()
+    }
+
+    /// The coefficients of the antiderivative whose constant term is `constant`: the term c·x^i
+    /// becomes c/(i+1)·x^(i+1).
+    pub fn integral(&self, constant: f64) -> Vec<f64> {
+        let mut coefficients = vec![constant];
+        coefficients.extend(self.coefficients.iter()
+            .enumerate()
+            .map(|(i, c)| c / (i + 1) as f64));
+        coefficients
+    }
+}
+
+use std::ops::{Add, Mul};
+
+/// Polynomials of the same degree add term by term.
+impl<const N: usize> Add for Polynomial<N> {
+    type Output = Polynomial<N>;
+    fn add(self, rhs: Polynomial<N>) -> Polynomial<N> {
+        let mut coefficients = self.coefficients;
+        for (c, r) in coefficients.iter_mut().zip(rhs.coefficients) {
+            *c += r;
+        }
+        Polynomial { coefficients }
+    }
+}
+
+/// Scaling a polynomial scales every coefficient.
+impl<const N: usize> Mul<f64> for Polynomial<N> {
+    type Output = Polynomial<N>;
+    fn mul(self, factor: f64) -> Polynomial<N> {
+        Polynomial { coefficients: self.coefficients.map(|c| c * factor) }
+    }
+}
+
+#[test]
+fn test_eval_complex() {
+    // x² + 1 has its roots at ±i.
+    let p = Polynomial::new([1.0, 0.0, 1.0]);
+    assert!(p.eval_complex(Complex::new(0.0, 1.0)).norm() < 1e-12);
+    assert!(p.eval_complex(Complex::new(0.0, -1.0)).norm() < 1e-12);
+
+    // On the real axis it agrees with eval.
+    let at_two = p.eval_complex(Complex::new(2.0, 0.0));
+    assert_eq!(at_two, Complex::new(p.eval(2.0), 0.0));
+}
+
+#[test]
+fn test_add_and_scale() {
+    let sine_poly = Polynomial::new([0.0, 1.0, 0.0, -1.0/6.0, 0.0, 1.0/120.0]);
+    let q = Polynomial::new([2.0, -1.0, 0.5, 0.0, 3.0, 0.0]);
+
+    for &x in &[-1.5, 0.0, 0.25, 1.0, 3.0] {
+        assert!(((sine_poly + q).eval(x) - (sine_poly.eval(x) + q.eval(x))).abs() < 1e-9);
+        assert!(((sine_poly * 2.0).eval(x) - 2.0 * sine_poly.eval(x)).abs() < 1e-9);
+        assert!(((q * -0.5).eval(x) - -0.5 * q.eval(x)).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_derivative_of_sine_is_cosine() {
+    let sine_poly = Polynomial::new([0.0, 1.0, 0.0, -1.0/6.0, 0.0, 1.0/120.0]);
+    let cosine_poly = sine_poly.derivative();
+    assert_eq!(cosine_poly, vec![1.0, 0.0, -0.5, 0.0, 1.0/24.0]);
+
+    let cosine = Polynomial::<5>::new(cosine_poly.try_into().unwrap());
+    for &x in &[0.0, 0.1, -0.2, 0.5] {
+        assert!((cosine.eval(x) - f64::cos(x)).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn test_integral() {
+    // The integral of 1 + 2x + 3x² is C + x + x² + x³.
+    let p = Polynomial::new([1.0, 2.0, 3.0]);
+    assert_eq!(p.integral(5.0), vec![5.0, 1.0, 1.0, 1.0]);
+
+    // Integrating and then differentiating gets back where we started.
+    let integral = Polynomial::new([0.0, 1.0, 1.0, 1.0]);
+    assert_eq!(integral.derivative(), p.coefficients.to_vec());
 }
 
 
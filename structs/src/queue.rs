@@ -16,12 +16,12 @@ impl<T> Queue<T> {
             if self.younger.is_empty() {
                 return None;
             }
-        }
 
-        //Bring the elements in younger over to older, and put them in the promised order.
-        use std::mem::swap;
-        swap(&mut self.older, &mut self.younger);
-        self.older.reverse();
+            //Bring the elements in younger over to older, and put them in the promised order.
+            use std::mem::swap;
+            swap(&mut self.older, &mut self.younger);
+            self.older.reverse();
+        }
 
         self.older.pop()
     }
@@ -32,11 +32,32 @@ impl<T> Queue<T> {
         self.older.is_empty() && self.younger.is_empty()
     }
 
+    /// Return the element `pop` would return next, without removing it. The front of the queue is
+    /// the end of `older`, or if that's empty, the start of `younger`.
+    pub fn peek(&self) -> Option<&T> {
+        self.older.last().or_else(|| self.younger.first())
+    }
+
+    /// The number of elements in the queue, wherever they currently sit.
+    pub fn len(&self) -> usize {
+        self.older.len() + self.younger.len()
+    }
+
     /// If a method wants to take ownership of self, it can take self by value.
     pub fn split(self) -> (Vec<T>, Vec<T>) {
         (self.older, self.younger)
     }
 
+    /// Consume the queue, returning its elements in the order repeated calls to `pop` would
+    /// yield them. `older` is kept in reverse order, so it has to be flipped before the
+    /// elements of `younger` are appended.
+    pub fn into_vec(self) -> Vec<T> {
+        let mut elements = self.older;
+        elements.reverse();
+        elements.extend(self.younger);
+        elements
+    }
+
     /// An impl block for a given type can also define functions that don't take self as an argument
     /// at all. These are still associated functions, since they're in an impl block, but they're
     /// not methods, since they don't take a self argument. These are called type-associated functions.
@@ -45,6 +66,39 @@ impl<T> Queue<T> {
     }
 }
 
+/// Cloning a queue gives an independent snapshot of it: both internal vectors are cloned, so
+/// popping from the copy leaves the original intact.
+impl<T: Clone> Clone for Queue<T> {
+    fn clone(&self) -> Self {
+        Queue { older: self.older.clone(), younger: self.younger.clone() }
+    }
+}
+
+/// Iterating over a queue by value consumes it, producing the elements in the same order `pop`
+/// would. `older` already holds the front of the queue, last element first, so the iterator pops
+/// from it directly, then moves on to `younger` from the start.
+impl<T> IntoIterator for Queue<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { older: self.older, younger: self.younger.into_iter() }
+    }
+}
+
+pub struct IntoIter<T> {
+    older: Vec<T>,
+    younger: std::vec::IntoIter<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.older.pop().or_else(|| self.younger.next())
+    }
+}
+
 /// This impl block header reads, Here are some associated functions specifically for Queue<f64>.
 /// This gives Queue<f64> a sum method, available on no other kind of Queue.
 impl Queue<f64> {
@@ -53,6 +107,137 @@ impl Queue<f64> {
     }
 }
 
+#[test]
+fn test_pop_interleaved() {
+    // `pop` must only move `younger` over to `older` once `older` has run dry. Flipping on every
+    // call would put elements pushed after a pop ahead of ones that were already waiting.
+    let mut q = Queue::new();
+    q.push(1);
+    q.push(2);
+    assert_eq!(q.pop(), Some(1));
+    q.push(3);
+    assert_eq!(q.pop(), Some(2));
+    q.push(4);
+    q.push(5);
+    assert_eq!(q.pop(), Some(3));
+    assert_eq!(q.pop(), Some(4));
+    q.push(6);
+    assert_eq!(q.pop(), Some(5));
+    assert_eq!(q.pop(), Some(6));
+    assert_eq!(q.pop(), None);
+}
+
+#[test]
+fn test_into_vec() {
+    let mut q = Queue::new();
+    q.push(1);
+    q.push(2);
+    q.push(3);
+    assert_eq!(q.pop(), Some(1));
+
+    // Splitting and reassembling the queue leaves its contents untouched.
+    let (older, younger) = q.split();
+    let mut q = Queue { older, younger };
+
+    q.push(4);
+    assert_eq!(q.pop(), Some(2));
+    q.push(5);
+
+    // The remaining elements are spread across both vectors, so `into_vec` has to combine them.
+    let (older, younger) = q.split();
+    assert_eq!((older.len(), younger.len()), (1, 2));
+    let q = Queue { older, younger };
+
+    assert_eq!(q.into_vec(), vec![3, 4, 5]);
+}
+
+#[test]
+fn test_clone() {
+    let mut q = Queue::new();
+    q.push('a');
+    q.push('b');
+    assert_eq!(q.pop(), Some('a'));
+    q.push('c');
+
+    let mut snapshot = q.clone();
+    assert_eq!(snapshot.pop(), Some('b'));
+    assert_eq!(snapshot.pop(), Some('c'));
+    assert!(snapshot.is_empty());
+
+    assert_eq!(q.into_vec(), vec!['b', 'c']);
+}
+
+#[test]
+fn test_len() {
+    let mut q = Queue::new();
+    assert_eq!(q.len(), 0);
+    assert!(q.is_empty());
+
+    for (i, c) in "abc".chars().enumerate() {
+        q.push(c);
+        assert_eq!(q.len(), i + 1);
+    }
+
+    // The first pop moves everything from younger over to older.
+    assert_eq!(q.pop(), Some('a'));
+    assert_eq!(q.len(), 2);
+
+    q.push('d');
+    assert_eq!(q.len(), 3);
+
+    assert_eq!(q.pop(), Some('b'));
+    assert_eq!(q.pop(), Some('c'));
+    assert_eq!(q.len(), 1);
+
+    // Emptying older makes the next pop flip again.
+    assert_eq!(q.pop(), Some('d'));
+    assert_eq!(q.len(), 0);
+    assert!(q.is_empty());
+}
+
+#[test]
+fn test_peek() {
+    let mut q = Queue::new();
+    assert_eq!(q.peek(), None);
+
+    q.push('x');
+    q.push('y');
+    assert_eq!(q.peek(), Some(&'x'));
+    assert_eq!(q.pop(), Some('x'));
+
+    q.push('z');
+    assert_eq!(q.peek(), Some(&'y'));
+    assert_eq!(q.pop(), Some('y'));
+    assert_eq!(q.peek(), Some(&'z'));
+    assert_eq!(q.pop(), Some('z'));
+    assert_eq!(q.peek(), None);
+}
+
+#[test]
+fn test_into_iter() {
+    let mut q = Queue::new();
+    let mut expected = Queue::new();
+    for (i, c) in "queue".chars().enumerate() {
+        q.push(c);
+        expected.push(c);
+        if i % 2 == 1 {
+            q.pop();
+            expected.pop();
+        }
+    }
+
+    let mut popped = Vec::new();
+    while let Some(c) = expected.pop() {
+        popped.push(c);
+    }
+
+    let mut iterated = Vec::new();
+    for c in q {
+        iterated.push(c);
+    }
+    assert_eq!(iterated, popped);
+    assert_eq!(iterated, vec!['e', 'u', 'e']);
+}
 
 pub(in crate) struct Extrema<'elt> {
     pub greatest: &'elt i32,
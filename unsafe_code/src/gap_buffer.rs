@@ -73,6 +73,17 @@ impl <T> GapBuffer<T> {
             None
         }
     }
+
+    /// Iterate over the elements in order: those before the gap, then those after it. Only raw
+    /// indices outside the gap are ever produced, so the uninitialized middle is never touched.
+    pub fn iter(&self) -> impl Iterator<Item=&T> {
+        (0..self.gap.start).chain(self.gap.end..self.capacity())
+            .map(move |raw| unsafe {
+                // `raw` is below the capacity and outside the gap, so it refers to a live element.
+                &*self.space(raw)
+            })
+    }
+
     /// When we start making insertions and deletions in a different part of the buffer, we need to
     /// move the gap to the new location. Moving the gap to the right entails shifting elements to the
     /// left, and vice versa, just as the bubble in a spirit level moves in one direction when the fluid
@@ -146,6 +157,55 @@ impl <T> GapBuffer<T> {
         Some(element)
     }
 
+    /// Append `elt` to the end of the buffer, leaving the insertion position after it.
+    pub fn push_back(&mut self, elt: T) {
+        self.set_position(self.len());
+        self.insert(elt);
+    }
+
+    /// Remove the last element and return it, or return `None` if the buffer is empty. This leaves
+    /// the insertion position at the end.
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len() == 0 {
+            return None;
+        }
+        self.set_position(self.len() - 1);
+        self.remove()
+    }
+
+    /// Remove up to `count` elements just after the insertion position, dropping them, and return
+    /// how many were actually removed. This is fewer than `count` when the insertion position is
+    /// within `count` elements of the end.
+    pub fn remove_range(&mut self, count: usize) -> usize {
+        let removed = count.min(self.capacity() - self.gap.end);
+        let start = self.gap.end;
+
+        // Enlarge the gap before dropping anything: if a destructor panics, the buffer's own Drop
+        // must not try to drop the elements that were already dropped here. At worst the rest
+        // of the removed elements leak.
+        self.gap.end += removed;
+        unsafe {
+            for i in start..start + removed {
+                std::ptr::drop_in_place(self.space_mut(i));
+            }
+        }
+        removed
+    }
+
+    /// Drop every element, leaving the buffer empty but keeping its storage for reuse.
+    pub fn clear(&mut self) {
+        let old_gap = self.gap.clone();
+
+        // As in remove_range, mark the elements as gone before dropping them, so a panicking
+        // destructor can't lead to a double drop.
+        self.gap = 0..self.capacity();
+        unsafe {
+            for i in (0..old_gap.start).chain(old_gap.end..self.capacity()) {
+                std::ptr::drop_in_place(self.space_mut(i));
+            }
+        }
+    }
+
     // Double the capacity of `self.storage`
     fn enlarge_gap(&mut self) {
         let mut new_capacity = self.capacity() * 2;
@@ -176,6 +236,34 @@ impl <T> GapBuffer<T> {
 }
 
 
+/// Build a buffer holding the characters of `text`, with the insertion position at the end, as if
+/// the text had just been typed in.
+impl From<&str> for GapBuffer<char> {
+    fn from(text: &str) -> GapBuffer<char> {
+        let mut buf = GapBuffer::new();
+        buf.insert_iter(text.chars());
+        buf
+    }
+}
+
+impl GapBuffer<char> {
+    /// Return the index of the first occurrence of `needle`, or `None` if it doesn't appear. Since
+    /// this goes through `get`, which works in terms of element indices rather than raw storage
+    /// offsets, a match that straddles the gap is found like any other.
+    pub fn find(&self, needle: &str) -> Option<usize> {
+        let needle: Vec<char> = needle.chars().collect();
+        if needle.len() > self.len() {
+            return None;
+        }
+
+        (0..=self.len() - needle.len()).find(|&start| {
+            needle.iter()
+                .enumerate()
+                .all(|(i, c)| self.get(start + i) == Some(c))
+        })
+    }
+}
+
 impl<T> Drop for GapBuffer<T> {
     /// The elements lie before and after the gap, so we iterate over each region and use the
     /// std::ptr::drop_in_place function to drop each one. The drop_in_place function is a utility
@@ -194,3 +282,134 @@ impl<T> Drop for GapBuffer<T> {
         }
     }
 }
+
+
+#[test]
+fn test_iter() {
+    let mut buf = GapBuffer::new();
+    buf.insert_iter("Lord of the Rings".chars());
+    buf.set_position(12);
+    buf.insert_iter("Fellowship of the ".chars());
+    assert_eq!(buf.iter().collect::<String>(), "Lord of the Fellowship of the Rings");
+
+    // Wherever the gap is, the iterator produces the same sequence.
+    for pos in [0, 5, 30, buf.len()] {
+        buf.set_position(pos);
+        assert_eq!(buf.iter().collect::<String>(), "Lord of the Fellowship of the Rings");
+    }
+
+    let empty: GapBuffer<char> = GapBuffer::new();
+    assert_eq!(empty.iter().next(), None);
+}
+
+#[test]
+fn test_remove_range() {
+    use std::rc::Rc;
+
+    let token = Rc::new(());
+    let mut buf = GapBuffer::new();
+    for i in 0..6 {
+        buf.insert((i.to_string(), token.clone()));
+    }
+    assert_eq!(Rc::strong_count(&token), 7);
+
+    buf.set_position(1);
+    assert_eq!(buf.remove_range(3), 3);
+    assert_eq!(buf.len(), 3);
+    assert_eq!(Rc::strong_count(&token), 4);
+    assert_eq!(buf.iter().map(|(s, _)| s.as_str()).collect::<Vec<_>>(), vec!["0", "4", "5"]);
+
+    // Only two elements follow the insertion position, so only two can be removed.
+    assert_eq!(buf.remove_range(10), 2);
+    assert_eq!(buf.len(), 1);
+    assert_eq!(buf.remove_range(1), 0);
+    assert_eq!(Rc::strong_count(&token), 2);
+
+    drop(buf);
+    assert_eq!(Rc::strong_count(&token), 1);
+}
+
+#[test]
+fn test_from_str() {
+    let mut buf = GapBuffer::from("hello");
+    assert_eq!(buf.len(), 5);
+    assert_eq!(buf.position(), 5);
+    assert_eq!(buf.get(0), Some(&'h'));
+
+    buf.set_position(0);
+    buf.insert('X');
+    assert_eq!(buf.iter().collect::<String>(), "Xhello");
+}
+
+#[test]
+fn test_find() {
+    let mut buf = GapBuffer::from("the quick brown fox");
+
+    // The gap sits at the end, after every match.
+    assert_eq!(buf.find("quick"), Some(4));
+    assert_eq!(buf.find("fox"), Some(16));
+
+    // Put the gap in the middle of "brown".
+    buf.set_position(12);
+    assert_eq!(buf.find("brown"), Some(10));
+    assert_eq!(buf.find("the"), Some(0));
+    assert_eq!(buf.find("fox"), Some(16));
+
+    // Put the gap at the start, before every match.
+    buf.set_position(0);
+    assert_eq!(buf.find("quick"), Some(4));
+
+    assert_eq!(buf.find("slow"), None);
+    assert_eq!(buf.find("the quick brown fox jumps"), None);
+    assert_eq!(buf.find(""), Some(0));
+}
+
+#[test]
+fn test_push_and_pop_back() {
+    let mut buf = GapBuffer::new();
+    assert_eq!(buf.pop_back(), None);
+
+    for i in 1..=5 {
+        buf.push_back(i);
+    }
+    assert_eq!(buf.pop_back(), Some(5));
+    assert_eq!(buf.pop_back(), Some(4));
+
+    // Moving the insertion position elsewhere doesn't change where the back is.
+    buf.set_position(0);
+    buf.insert(0);
+    buf.push_back(6);
+    assert_eq!(buf.iter().cloned().collect::<Vec<_>>(), vec![0, 1, 2, 3, 6]);
+
+    buf.set_position(2);
+    assert_eq!(buf.pop_back(), Some(6));
+    assert_eq!(buf.pop_back(), Some(3));
+    assert_eq!(buf.len(), 3);
+    assert_eq!(buf.position(), 3);
+}
+
+#[test]
+fn test_clear() {
+    use std::rc::Rc;
+
+    let token = Rc::new(());
+    let mut buf = GapBuffer::new();
+    for _ in 0..5 {
+        buf.insert(token.clone());
+    }
+    buf.set_position(2);
+    let capacity = buf.capacity();
+    assert_eq!(Rc::strong_count(&token), 6);
+
+    buf.clear();
+    assert_eq!(Rc::strong_count(&token), 1);
+    assert_eq!(buf.len(), 0);
+    assert_eq!(buf.position(), 0);
+    assert_eq!(buf.capacity(), capacity);
+
+    // The buffer is still usable, and dropping it afterwards drops only the new elements.
+    buf.insert(token.clone());
+    assert_eq!(Rc::strong_count(&token), 2);
+    drop(buf);
+    assert_eq!(Rc::strong_count(&token), 1);
+}
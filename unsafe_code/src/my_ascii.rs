@@ -59,13 +59,96 @@ impl Ascii {
     pub unsafe fn from_bytes_unchecked(bytes: Vec<u8>) -> Ascii {
         Ascii(bytes)
     }
+
+    /// Borrow the text as a `&str`, without consuming the `Ascii` the way converting to a
+    /// `String` does.
+    pub fn as_str(&self) -> &str {
+        // Safe for the same reason as the `From<Ascii> for String` conversion: well-formed ASCII
+        // text is also well-formed UTF-8.
+        unsafe { std::str::from_utf8_unchecked(&self.0) }
+    }
+}
+
+impl Ascii {
+    /// Append `byte`, if it is ASCII. Otherwise, leave the text unchanged and hand the byte back in
+    /// a `NotAsciiError`. Checking before every append is what lets `push` be a safe function: no
+    /// call to it can break the invariant `as_str` and the `String` conversion rely on.
+    pub fn push(&mut self, byte: u8) -> Result<(), NotAsciiError> {
+        if !byte.is_ascii() {
+            return Err(NotAsciiError(vec![byte]));
+        }
+        self.0.push(byte);
+        Ok(())
+    }
+
+    /// Append all of `s`, if it is entirely ASCII. Otherwise append nothing, and return the bytes
+    /// of `s` in a `NotAsciiError`, just as `from_bytes` does.
+    pub fn push_str(&mut self, s: &str) -> Result<(), NotAsciiError> {
+        if !s.is_ascii() {
+            return Err(NotAsciiError(s.as_bytes().to_vec()));
+        }
+        self.0.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
 }
 
-/// When conversion fails, we give back the vector we couldn't convert. This should implement
-/// `std::error::Error`; omitted for brevity
+#[test]
+fn test_push() {
+    let mut ascii = Ascii::from_bytes(b"caf".to_vec()).unwrap();
+    assert_eq!(ascii.push(b'e'), Ok(()));
+    assert_eq!(ascii.as_str(), "cafe");
+
+    assert_eq!(ascii.push(0xe9), Err(NotAsciiError(vec![0xe9])));
+    assert_eq!(ascii.as_str(), "cafe");
+
+    assert_eq!(ascii.push_str(" au lait"), Ok(()));
+    assert_eq!(ascii.push_str(" crème"), Err(NotAsciiError(" crème".as_bytes().to_vec())));
+    assert_eq!(ascii.as_str(), "cafe au lait");
+}
+
+impl std::fmt::Display for Ascii {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[test]
+fn test_as_str_and_display() {
+    let ascii = Ascii::from_bytes(b"ASCII and ye shall receive".to_vec()).unwrap();
+    assert_eq!(ascii.as_str(), "ASCII and ye shall receive");
+    assert_eq!(format!("{}", ascii), "ASCII and ye shall receive");
+
+    // Neither of those consumed `ascii`.
+    assert_eq!(String::from(ascii), "ASCII and ye shall receive");
+}
+
+/// When conversion fails, we give back the vector we couldn't convert.
 #[derive(Debug, Eq, PartialEq)]
 pub struct NotAsciiError(pub Vec<u8>);
 
+impl std::fmt::Display for NotAsciiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let count = self.0.iter().filter(|byte| !byte.is_ascii()).count();
+        match self.0.iter().position(|byte| !byte.is_ascii()) {
+            Some(first) => write!(f, "found {} non-ASCII byte(s), the first at index {}", count, first),
+            None => write!(f, "found no non-ASCII bytes"),
+        }
+    }
+}
+
+impl std::error::Error for NotAsciiError {}
+
+#[test]
+fn test_not_ascii_error() {
+    fn parse(bytes: &[u8]) -> Result<Ascii, Box<dyn std::error::Error>> {
+        Ok(Ascii::from_bytes(bytes.to_vec())?)
+    }
+
+    let error = parse("naïve café".as_bytes()).unwrap_err();
+    assert_eq!(error.to_string(), "found 4 non-ASCII byte(s), the first at index 2");
+    assert!(parse(b"naive").is_ok());
+}
+
 // Safe, efficient conversion, implemented using unsafe code.
 impl From<Ascii> for String {
     /// # Unsafe Block or Unsafe function?
@@ -120,6 +203,9 @@ unsafe impl Zeroable for u16 {}
 unsafe impl Zeroable for i16 {}
 unsafe impl Zeroable for isize {}
 unsafe impl Zeroable for i8 {}
+// An all-zero bit pattern is positive zero in IEEE 754.
+unsafe impl Zeroable for f32 {}
+unsafe impl Zeroable for f64 {}
 
 
 pub fn zeroed_vector<T>(len: usize) -> Vec<T>
@@ -132,3 +218,25 @@ pub fn zeroed_vector<T>(len: usize) -> Vec<T>
     }
     vec
 }
+
+/// A `rows` by `cols` matrix of zeros. The `Zeroable` bound carries over from `zeroed_vector`, so
+/// asking for a matrix of some type that has no all-zero value is a compile-time error.
+pub fn zeroed_matrix<T>(rows: usize, cols: usize) -> Vec<Vec<T>>
+    where T: Zeroable
+{
+    (0..rows).map(|_| zeroed_vector(cols)).collect()
+}
+
+#[test]
+fn test_zeroed_matrix() {
+    let matrix: Vec<Vec<f64>> = zeroed_matrix(3, 4);
+    assert_eq!(matrix.len(), 3);
+    assert!(matrix.iter().all(|row| row.len() == 4));
+    assert!(matrix.iter().flatten().all(|&x| x == 0.0));
+
+    let floats: Vec<f32> = zeroed_vector(5);
+    assert_eq!(floats, vec![0.0; 5]);
+
+    let empty: Vec<Vec<u8>> = zeroed_matrix(0, 10);
+    assert!(empty.is_empty());
+}
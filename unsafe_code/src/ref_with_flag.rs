@@ -98,3 +98,125 @@ impl<'a, T: 'a> RefWithFlag<'a, T> {
         self.ptr_and_bit & 1 != 0
     }
 }
+
+/// A `&mut T` and a `bool`, wrapped up in a single word. This is RefWithFlag for exclusive
+/// references: the PhantomData claims a `&'a mut T`, so Rust treats a RefWithFlagMut as the one
+/// and only way to reach the referent for as long as it lives.
+pub struct RefWithFlagMut<'a, T> {
+    ptr_and_bit: usize,
+    behaves_like: PhantomData<&'a mut T>
+}
+
+impl<'a, T: 'a> RefWithFlagMut<'a, T> {
+    pub fn new(ptr: &'a mut T, flag: bool) -> RefWithFlagMut<'a, T> {
+        assert_eq!(align_of::<T>() % 2, 0);
+        RefWithFlagMut {
+            ptr_and_bit: ptr as *mut T as usize | flag as usize,
+            behaves_like: PhantomData
+        }
+    }
+
+    /// Since T is at least two-byte aligned, the low bit of any real address of a T is zero, and
+    /// the flag is the only thing ever stored there. Masking it off recovers exactly the pointer
+    /// we were given. Taking `&mut self` ensures at most one mutable reference is live at once.
+    pub fn get_mut(&mut self) -> &mut T {
+        unsafe {
+            let ptr = (self.ptr_and_bit & !1) as *mut T;
+            &mut *ptr
+        }
+    }
+
+    pub fn get_flag(&self) -> bool {
+        self.ptr_and_bit & 1 != 0
+    }
+}
+
+#[test]
+fn test_ref_with_flag_mut() {
+    let mut vec = vec![10, 20, 30];
+    {
+        let mut r = RefWithFlagMut::new(&mut vec, true);
+        r.get_mut().push(40);
+        r.get_mut()[0] = 5;
+        assert_eq!(r.get_mut().len(), 4);
+        assert!(r.get_flag());
+    }
+    assert_eq!(vec, [5, 20, 30, 40]);
+}
+
+/// A `&T` and a few bits of flags, wrapped up in a single word. A type aligned to 2^n bytes leaves
+/// the low n bits of every pointer to it zero, so RefWithFlags can store that many flag bits; for
+/// a `u64`, that is three.
+pub struct RefWithFlags<'a, T> {
+    ptr_and_flags: usize,
+    /// How many of the low bits hold flags. Always at most `align_of::<T>().trailing_zeros()`.
+    flag_bits: u32,
+    behaves_like: PhantomData<&'a T>
+}
+
+impl<'a, T: 'a> RefWithFlags<'a, T> {
+    /// Wrap `ptr`, reserving `flag_bits` bits for flags, all initially clear. Panic if `T`'s
+    /// alignment doesn't leave that many bits free, or if they wouldn't fit in a `u8`.
+    pub fn new(ptr: &'a T, flag_bits: u32) -> RefWithFlags<'a, T> {
+        assert!(flag_bits <= align_of::<T>().trailing_zeros().min(u8::BITS),
+                "alignment of {} bytes leaves no room for {} flag bits in a u8",
+                align_of::<T>(), flag_bits);
+        RefWithFlags {
+            ptr_and_flags: ptr as *const T as usize,
+            flag_bits,
+            behaves_like: PhantomData
+        }
+    }
+
+    fn mask(&self) -> usize {
+        (1 << self.flag_bits) - 1
+    }
+
+    pub fn get_ref(&self) -> &'a T {
+        unsafe {
+            let ptr = (self.ptr_and_flags & !self.mask()) as *const T;
+            &*ptr
+        }
+    }
+
+    pub fn get_flags(&self) -> u8 {
+        (self.ptr_and_flags & self.mask()) as u8
+    }
+
+    /// Replace all the flags with `flags`. Panic if `flags` has bits set beyond the reserved ones,
+    /// since storing them would corrupt the pointer.
+    pub fn set_flags(&mut self, flags: u8) {
+        assert!(flags as usize & !self.mask() == 0,
+                "flags {:#b} don't fit in {} bits", flags, self.flag_bits);
+        self.ptr_and_flags = (self.ptr_and_flags & !self.mask()) | flags as usize;
+    }
+}
+
+#[test]
+fn test_ref_with_flags() {
+    const A: u8 = 0b001;
+    const B: u8 = 0b010;
+    const C: u8 = 0b100;
+
+    let value: u64 = 0x0123_4567_89ab_cdef;
+    let mut r = RefWithFlags::new(&value, 3);
+    assert_eq!(r.get_flags(), 0);
+
+    r.set_flags(A | C);
+    assert_eq!(r.get_flags() & A, A);
+    assert_eq!(r.get_flags() & B, 0);
+    assert_eq!(r.get_flags() & C, C);
+    assert_eq!(*r.get_ref(), 0x0123_4567_89ab_cdef);
+
+    r.set_flags(r.get_flags() | B);
+    r.set_flags(r.get_flags() & !A);
+    assert_eq!(r.get_flags(), B | C);
+    assert_eq!(*r.get_ref(), 0x0123_4567_89ab_cdef);
+}
+
+#[test]
+#[should_panic]
+fn test_ref_with_flags_too_many_bits() {
+    let value: u16 = 7;
+    RefWithFlags::new(&value, 2);
+}
@@ -21,10 +21,79 @@ pub(crate) struct Appellation {
 /// method receives is always still fully initialized.
 impl Drop for Appellation {
     fn drop (&mut self) {
-        print!("Dropping {}", self.name);
+        if !DROP_LOGGING.with(|enabled| enabled.get()) {
+            return;
+        }
+
+        DROP_SINK.with(|sink| {
+            let mut sink = sink.borrow_mut();
+            // A destructor has nowhere to report a failed write, so the error is ignored.
+            let _ = self.log_drop(&mut *sink);
+        });
+    }
+}
+
+impl Appellation {
+    fn log_drop(&self, out: &mut dyn Write) -> std::io::Result<()> {
+        write!(out, "Dropping {}", self.name)?;
         if !self.nicknames.is_empty() {
-            print!(" (AKA {})", self.nicknames.join(", "));
+            write!(out, " (AKA {})", self.nicknames.join(", "))?;
         }
-        println!();
+        writeln!(out)
     }
 }
+
+use std::cell::{Cell, RefCell};
+use std::io::Write;
+
+// Printing from drop makes every test that lets an Appellation go out of scope noisy, so logging
+// is off unless explicitly enabled. Both settings are thread-local, which keeps tests running in
+// parallel from seeing each other's output.
+thread_local! {
+    static DROP_LOGGING: Cell<bool> = const { Cell::new(false) };
+    static DROP_SINK: RefCell<Box<dyn Write>> = RefCell::new(Box::new(std::io::stdout()));
+}
+
+/// Turn logging of dropped Appellations on or off for the current thread.
+pub(crate) fn set_drop_logging(enabled: bool) {
+    DROP_LOGGING.with(|flag| flag.set(enabled));
+}
+
+/// Send drop logging for the current thread to `sink` instead of standard output, returning the
+/// sink that was in use before.
+pub(crate) fn set_drop_sink(sink: Box<dyn Write>) -> Box<dyn Write> {
+    DROP_SINK.with(|current| std::mem::replace(&mut *current.borrow_mut(), sink))
+}
+
+#[test]
+fn test_drop_logging() {
+    use std::rc::Rc;
+
+    /// A sink that shares its buffer, so the test can inspect what was written after handing the
+    /// sink itself over.
+    struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let buffer = Rc::new(RefCell::new(Vec::new()));
+    set_drop_sink(Box::new(SharedBuffer(buffer.clone())));
+
+    drop(Appellation { name: "Hera".to_string(), nicknames: vec![] });
+    assert!(buffer.borrow().is_empty());
+
+    set_drop_logging(true);
+    drop(Appellation { name: "Zeus".to_string(), nicknames: vec!["cloud collector".to_string()] });
+    assert_eq!(String::from_utf8(buffer.borrow().clone()).unwrap(),
+               "Dropping Zeus (AKA cloud collector)\n");
+
+    set_drop_logging(false);
+    drop(Appellation { name: "Hera".to_string(), nicknames: vec![] });
+    assert_eq!(buffer.borrow().len(), "Dropping Zeus (AKA cloud collector)\n".len());
+}
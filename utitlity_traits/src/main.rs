@@ -7,6 +7,7 @@ use crate::deref::Selector;
 use crate::drop::*;
 
 fn main() {
+    set_drop_logging(true);
     let mut a = Appellation{
         name: "Zeus".to_string(),
         nicknames: vec!["cloud collector".to_string(),
@@ -259,3 +260,35 @@ fn describe(error: &Error) -> Cow<'static, str> {
         }
     }
 }
+
+/// A stable numeric code for each error, for callers that want something machine-readable rather
+/// than the human-oriented message from describe. Codes must never be reassigned once handed out.
+fn error_code(error: &Error) -> u32 {
+    match *error {
+        Error::OutOfMemory => 1,
+        Error::StackOverflow => 2,
+        Error::MachineOnFire => 3,
+        Error::Unfathomable => 4,
+        Error::FileNotFound(_) => 5,
+    }
+}
+
+/// Both the code and the message for an error.
+fn describe_full(error: &Error) -> (u32, Cow<'static, str>) {
+    (error_code(error), describe(error))
+}
+
+#[test]
+fn test_error_codes() {
+    assert_eq!(error_code(&Error::OutOfMemory), 1);
+    assert_eq!(error_code(&Error::StackOverflow), 2);
+    assert_eq!(error_code(&Error::MachineOnFire), 3);
+    assert_eq!(error_code(&Error::Unfathomable), 4);
+    assert_eq!(error_code(&Error::FileNotFound(Path::new("c:/af/bdf"))), 5);
+
+    let (code, message) = describe_full(&Error::FileNotFound(Path::new("c:/af/bdf")));
+    assert_eq!(code, 5);
+    assert_eq!(message, "file not found: c:/af/bdf");
+
+    assert_eq!(describe_full(&Error::MachineOnFire), (3, Cow::Borrowed("machine on fire")));
+}